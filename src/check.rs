@@ -0,0 +1,313 @@
+//! Validates supplemental content against an API dump, catching mistakes that
+//! would otherwise silently disappear from the generated documentation.
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Write},
+};
+
+use pulldown_cmark::{Event, Parser, Tag};
+
+use crate::{
+    dump::Dump,
+    supplement::{ItemDescription, SourceLocation, SupplementalData},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(formatter, "error"),
+            Severity::Warning => write!(formatter, "warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The item's target isn't a class, member, enum, or enum item.
+    UnknownTarget,
+
+    /// An entry in `ReturnTypes` doesn't name a type that appears in the dump.
+    UnknownReturnType(String),
+
+    /// A parameter's `Type` doesn't name a type that appears in the dump.
+    UnknownParameterType {
+        parameter: String,
+        type_name: String,
+    },
+
+    /// The item has no prose.
+    EmptyProse,
+
+    /// The item is a stub from `scaffold` that hasn't been filled in.
+    Stub,
+
+    /// The prose links to an anchor that the generated docs won't contain.
+    BrokenAnchor(String),
+}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub location: SourceLocation,
+    pub target: String,
+    pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}: {}: ", self.location, self.severity)?;
+
+        match &self.kind {
+            DiagnosticKind::UnknownTarget => write!(
+                formatter,
+                "`{}` does not match any class, member, or enum",
+                self.target
+            ),
+            DiagnosticKind::UnknownReturnType(name) => write!(
+                formatter,
+                "return type `{}` of `{}` is not a known type",
+                name, self.target
+            ),
+            DiagnosticKind::UnknownParameterType {
+                parameter,
+                type_name,
+            } => write!(
+                formatter,
+                "type `{}` of parameter `{}` of `{}` is not a known type",
+                type_name, parameter, self.target
+            ),
+            DiagnosticKind::EmptyProse => {
+                write!(formatter, "`{}` has an empty description", self.target)
+            }
+            DiagnosticKind::Stub => write!(
+                formatter,
+                "`{}` is a stub and won't be used until it's filled in",
+                self.target
+            ),
+            DiagnosticKind::BrokenAnchor(anchor) => write!(
+                formatter,
+                "`{}` links to `#{}`, which does not exist",
+                self.target, anchor
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CheckReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CheckReport {
+    pub fn new(dump: &Dump, content: &SupplementalData) -> CheckReport {
+        let targets = collect_targets(dump);
+        let types = dump.named_types();
+
+        let mut items: Vec<&ItemDescription> = content.item_descriptions.values().collect();
+        items.sort_by(|a, b| {
+            a.location
+                .path
+                .cmp(&b.location.path)
+                .then(a.location.line.cmp(&b.location.line))
+        });
+
+        let mut diagnostics = Vec::new();
+
+        for item in items {
+            let mut report = |severity, kind| {
+                diagnostics.push(Diagnostic {
+                    severity,
+                    location: item.location.clone(),
+                    target: item.metadata.target.clone(),
+                    kind,
+                })
+            };
+
+            if !targets.contains(&item.metadata.target) {
+                report(Severity::Error, DiagnosticKind::UnknownTarget);
+            }
+
+            for type_name in item.metadata.return_types.iter().flatten() {
+                if !types.contains_key(type_name) {
+                    report(
+                        Severity::Error,
+                        DiagnosticKind::UnknownReturnType(type_name.clone()),
+                    );
+                }
+            }
+
+            let mut parameters: Vec<_> = item.metadata.parameters.iter().collect();
+            parameters.sort_by_key(|(name, _)| name.as_str());
+
+            for (name, parameter) in parameters {
+                if let Some(type_name) = &parameter.kind {
+                    if !types.contains_key(type_name) {
+                        report(
+                            Severity::Error,
+                            DiagnosticKind::UnknownParameterType {
+                                parameter: name.clone(),
+                                type_name: type_name.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+
+            if item.metadata.stub {
+                report(Severity::Warning, DiagnosticKind::Stub);
+            } else if item.prose.is_empty() {
+                report(Severity::Warning, DiagnosticKind::EmptyProse);
+            }
+
+            for anchor in find_anchors(&item.prose) {
+                if !targets.contains(&anchor) {
+                    report(Severity::Error, DiagnosticKind::BrokenAnchor(anchor));
+                }
+            }
+        }
+
+        CheckReport { diagnostics }
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+}
+
+/// Writes the report as plain text, one diagnostic per line, followed by a
+/// summary.
+pub fn emit_text(report: &CheckReport, output: &mut String) -> fmt::Result {
+    for diagnostic in &report.diagnostics {
+        writeln!(output, "{}", diagnostic)?;
+    }
+
+    writeln!(
+        output,
+        "{} error(s), {} warning(s)",
+        report.count(Severity::Error),
+        report.count(Severity::Warning)
+    )
+}
+
+/// Collects every target that content can describe, which are also the
+/// anchors that miniwiki generates.
+fn collect_targets(dump: &Dump) -> HashSet<String> {
+    let mut targets = HashSet::new();
+
+    for class in &dump.classes {
+        for member in &class.members {
+            targets.insert(format!("{}.{}", class.name, member.get_name()));
+        }
+
+        targets.insert(class.name.clone());
+    }
+
+    for dump_enum in &dump.enums {
+        for item in &dump_enum.items {
+            targets.insert(format!("Enum.{}.{}", dump_enum.name, item.name));
+        }
+
+        targets.insert(format!("Enum.{}", dump_enum.name));
+    }
+
+    targets
+}
+
+/// Finds the intra-doc anchors, like `#Instance.Name`, linked to from a
+/// Markdown document.
+fn find_anchors(prose: &str) -> Vec<String> {
+    Parser::new(prose)
+        .filter_map(|event| match event {
+            Event::Start(Tag::Link(destination, _)) => destination
+                .strip_prefix('#')
+                .map(|anchor| anchor.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::{fs, io::Write};
+
+    #[test]
+    fn report_problems() {
+        let dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Members": [{
+                        "MemberType": "Function",
+                        "Name": "Resize",
+                        "Parameters": [],
+                        "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                        "Security": "None"
+                    }]
+                }],
+                "Enums": [{ "Name": "Material", "Items": [{ "Name": "Plastic", "Value": 256 }] }]
+            }"#,
+        )
+        .unwrap();
+
+        let directory = tempfile::tempdir().unwrap();
+        let mut file = fs::File::create(directory.path().join("content.md")).unwrap();
+        write!(
+            file,
+            "+++\nTarget = \"Part\"\n+++\nSee [Resize](#Part.Resize) and [Plastic](#Enum.Material.Plastic).\n\n\
+             +++\nTarget = \"Part.Resize\"\nReturnTypes = [\"bool\", \"Enum.Material\", \"Banana\"]\n\
+             [Parameters.material]\nType = \"Enum.Material\"\n[Parameters.size]\nType = \"Vector3\"\n+++\n\n\
+             +++\nTarget = \"Part.Explode\"\n+++\nLinks to [nothing](#Part.Nothing).\n\n\
+             +++\nTarget = \"Enum.Material\"\nStub = true\n+++\n"
+        )
+        .unwrap();
+
+        let content = SupplementalData::read_from_path(directory.path()).unwrap();
+        let report = CheckReport::new(&dump, &content);
+
+        let kinds: Vec<_> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.target.as_str(), diagnostic.kind.clone()))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    "Part.Resize",
+                    DiagnosticKind::UnknownReturnType("Banana".to_owned())
+                ),
+                (
+                    "Part.Resize",
+                    DiagnosticKind::UnknownParameterType {
+                        parameter: "size".to_owned(),
+                        type_name: "Vector3".to_owned(),
+                    }
+                ),
+                ("Part.Resize", DiagnosticKind::EmptyProse),
+                ("Part.Explode", DiagnosticKind::UnknownTarget),
+                (
+                    "Part.Explode",
+                    DiagnosticKind::BrokenAnchor("Part.Nothing".to_owned())
+                ),
+                ("Enum.Material", DiagnosticKind::Stub),
+            ]
+        );
+        assert_eq!(report.count(Severity::Error), 4);
+        assert_eq!(report.count(Severity::Warning), 2);
+        assert_eq!(report.diagnostics[0].location.line, 6);
+    }
+}
@@ -0,0 +1,455 @@
+//! Measures how much of the API has descriptions, and where those
+//! descriptions came from, so that writing work can go where it's needed.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+use ritz::{html, Fragment, HtmlContent, UnescapedText};
+use serde_derive::Serialize;
+
+use crate::dump::{ContentSource, Dump, DumpClass, DumpClassMember, DumpTags};
+
+static STYLE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/resources/coverage.css"
+));
+
+/// Which items are left out of the report.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CoverageFilter {
+    /// Leave out classes and members tagged `Deprecated`.
+    pub exclude_deprecated: bool,
+
+    /// Leave out classes and members tagged `Hidden` or `NotBrowsable`.
+    pub exclude_hidden: bool,
+}
+
+impl CoverageFilter {
+    fn includes(&self, tags: &DumpTags) -> bool {
+        if self.exclude_deprecated && tags.contains("Deprecated") {
+            return false;
+        }
+
+        if self.exclude_hidden && (tags.contains("Hidden") || tags.contains("NotBrowsable")) {
+            return false;
+        }
+
+        true
+    }
+}
+
+/// How many items of one kind have descriptions.
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Coverage {
+    pub total: usize,
+    pub described: usize,
+
+    /// How many of the described items got their description from each
+    /// source.
+    pub sources: BTreeMap<ContentSource, usize>,
+
+    /// How many items only have a description written by Dumpling's
+    /// heuristics, like "Deprecated in favor of ...". These don't count as
+    /// described, since nobody has written real documentation for them yet.
+    pub heuristic_only: usize,
+}
+
+impl Coverage {
+    fn record(&mut self, description: Option<&str>, source: Option<ContentSource>) {
+        self.total += 1;
+
+        if description.is_none() {
+            return;
+        }
+
+        if source == Some(ContentSource::Heuristic) {
+            self.heuristic_only += 1;
+            return;
+        }
+
+        self.described += 1;
+
+        if let Some(source) = source {
+            *self.sources.entry(source).or_insert(0) += 1;
+        }
+    }
+
+    fn add(&mut self, other: &Coverage) {
+        self.total += other.total;
+        self.described += other.described;
+        self.heuristic_only += other.heuristic_only;
+
+        for (source, count) in &other.sources {
+            *self.sources.entry(*source).or_insert(0) += count;
+        }
+    }
+
+    pub fn missing(&self) -> usize {
+        self.total - self.described
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.described as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}/{} ({:.0}%)",
+            self.described,
+            self.total,
+            self.percent()
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ClassCoverage {
+    pub name: String,
+    pub class: Coverage,
+    pub members: Coverage,
+    pub parameters: Coverage,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CoverageTotals {
+    pub classes: Coverage,
+    pub members: Coverage,
+    pub parameters: Coverage,
+    pub enums: Coverage,
+    pub enum_items: Coverage,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CoverageReport {
+    /// Coverage of each class, with the classes missing the most member
+    /// descriptions first.
+    pub classes: Vec<ClassCoverage>,
+    pub totals: CoverageTotals,
+}
+
+impl CoverageReport {
+    pub fn new(dump: &Dump, filter: CoverageFilter) -> CoverageReport {
+        let mut classes: Vec<ClassCoverage> = dump
+            .classes
+            .iter()
+            .filter(|class| filter.includes(&class.tags))
+            .map(|class| measure_class(class, filter))
+            .collect();
+
+        classes.sort_by(|a, b| {
+            b.members
+                .missing()
+                .cmp(&a.members.missing())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let mut totals = CoverageTotals::default();
+
+        for class in &classes {
+            totals.classes.add(&class.class);
+            totals.members.add(&class.members);
+            totals.parameters.add(&class.parameters);
+        }
+
+        for dump_enum in dump.enums.iter().filter(|item| filter.includes(&item.tags)) {
+            totals.enums.record(
+                dump_enum.description.as_deref(),
+                dump_enum.description_source,
+            );
+
+            for item in dump_enum
+                .items
+                .iter()
+                .filter(|item| filter.includes(&item.tags))
+            {
+                totals
+                    .enum_items
+                    .record(item.description.as_deref(), item.description_source);
+            }
+        }
+
+        CoverageReport { classes, totals }
+    }
+}
+
+fn measure_class(class: &DumpClass, filter: CoverageFilter) -> ClassCoverage {
+    let mut coverage = ClassCoverage {
+        name: class.name.clone(),
+        class: Coverage::default(),
+        members: Coverage::default(),
+        parameters: Coverage::default(),
+    };
+
+    coverage
+        .class
+        .record(class.description.as_deref(), class.description_source);
+
+    for member in class
+        .members
+        .iter()
+        .filter(|member| filter.includes(member.tags()))
+    {
+        // Unknown members can't be described, so counting them would only
+        // make coverage look worse.
+        if let DumpClassMember::Unknown(_) = member {
+            continue;
+        }
+
+        coverage
+            .members
+            .record(member.description(), member.description_source());
+
+        for parameter in member.parameters() {
+            coverage.parameters.record(
+                parameter.description.as_deref(),
+                parameter.description_source,
+            );
+        }
+    }
+
+    coverage
+}
+
+/// Writes the report as a plain text table, followed by overall totals.
+pub fn emit_table(report: &CoverageReport, output: &mut String) -> fmt::Result {
+    let name_width = report
+        .classes
+        .iter()
+        .map(|class| class.name.len())
+        .chain(Some("Class".len()))
+        .max()
+        .unwrap_or(0);
+
+    writeln!(
+        output,
+        "{:<width$}  {:<11}  {:<18}  Parameters",
+        "Class",
+        "Description",
+        "Members",
+        width = name_width
+    )?;
+
+    for class in &report.classes {
+        writeln!(
+            output,
+            "{:<width$}  {:<11}  {:<18}  {}",
+            class.name,
+            if class.class.described > 0 {
+                "yes"
+            } else {
+                "no"
+            },
+            class.members.to_string(),
+            class.parameters,
+            width = name_width
+        )?;
+    }
+
+    writeln!(output)?;
+
+    for (name, coverage) in total_rows(&report.totals) {
+        let line = format!(
+            "{:<10}  {:<18}  {}",
+            name,
+            coverage.to_string(),
+            render_sources_text(coverage)
+        );
+
+        writeln!(output, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+fn total_rows(totals: &CoverageTotals) -> Vec<(&'static str, &Coverage)> {
+    vec![
+        ("Classes", &totals.classes),
+        ("Members", &totals.members),
+        ("Parameters", &totals.parameters),
+        ("Enums", &totals.enums),
+        ("Enum Items", &totals.enum_items),
+    ]
+}
+
+fn render_sources_text(coverage: &Coverage) -> String {
+    let mut sources: Vec<_> = coverage
+        .sources
+        .iter()
+        .map(|(source, count)| format!("{}: {}", source, count))
+        .collect();
+
+    if coverage.heuristic_only > 0 {
+        sources.push(format!(
+            "{} only (not counted): {}",
+            ContentSource::Heuristic,
+            coverage.heuristic_only
+        ));
+    }
+
+    sources.join(", ")
+}
+
+/// Writes the report as a standalone HTML page.
+pub fn emit_html(report: &CoverageReport, output: &mut String) -> fmt::Result {
+    writeln!(output, "<!doctype html>")?;
+
+    let html = html!(
+        <html>
+            <head>
+                <meta charset="utf-8" />
+                <meta name="viewport" content="width=device-width,initial-scale=1,shrink-to-fit=no" />
+                <style>{ UnescapedText::new(STYLE) }</style>
+                <title>"Rodocs Coverage"</title>
+            </head>
+            <body>
+                <h1>"Documentation Coverage"</h1>
+                <table class="coverage-totals">
+                    <tr>
+                        <th>"Kind"</th>
+                        <th>"Described"</th>
+                        <th>"Sources"</th>
+                    </tr>
+                    { Fragment::new(total_rows(&report.totals).into_iter().map(|(name, coverage)| render_total_row(name, coverage))) }
+                </table>
+                <table class="coverage-classes">
+                    <tr>
+                        <th>"Class"</th>
+                        <th>"Description"</th>
+                        <th>"Members"</th>
+                        <th>"Parameters"</th>
+                    </tr>
+                    { Fragment::new(report.classes.iter().map(render_class_row)) }
+                </table>
+            </body>
+        </html>
+    );
+
+    write!(output, "{}", html)
+}
+
+fn render_total_row<'a>(name: &'a str, coverage: &Coverage) -> HtmlContent<'a> {
+    html!(
+        <tr>
+            <td>{ name }</td>
+            { render_coverage_cell(coverage) }
+            <td>{ render_sources_text(coverage) }</td>
+        </tr>
+    )
+}
+
+fn render_class_row(class: &ClassCoverage) -> HtmlContent<'_> {
+    let description = if class.class.described > 0 {
+        "yes"
+    } else {
+        "no"
+    };
+
+    html!(
+        <tr>
+            <td>{ class.name.as_str() }</td>
+            <td class={ format!("coverage-{}", description) }>{ description }</td>
+            { render_coverage_cell(&class.members) }
+            { render_coverage_cell(&class.parameters) }
+        </tr>
+    )
+}
+
+fn render_coverage_cell<'a>(coverage: &Coverage) -> HtmlContent<'a> {
+    let level = match coverage.percent() as u32 {
+        0..=49 => "coverage-low",
+        50..=99 => "coverage-partial",
+        _ => "coverage-full",
+    };
+
+    html!(
+        <td class={ level } title={ render_sources_text(coverage) }>
+            { coverage.to_string() }
+        </td>
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn count_descriptions() {
+        let mut dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Members": [
+                        {
+                            "MemberType": "Function",
+                            "Name": "Resize",
+                            "Parameters": [{ "Name": "normalId", "Type": { "Name": "NormalId", "Category": "Enum" } }],
+                            "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "resize",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                            "Security": "None",
+                            "Tags": ["Deprecated"]
+                        }
+                    ]
+                }],
+                "Enums": [{ "Name": "Material", "Items": [{ "Name": "Plastic", "Value": 256 }] }]
+            }"#,
+        )
+        .unwrap();
+
+        dump.classes[0].members[0]
+            .set_description("Resizes the part.".to_owned(), ContentSource::Supplemental);
+        dump.classes[0].members[1].set_description(
+            "Deprecated in favor of Resize.".to_owned(),
+            ContentSource::Heuristic,
+        );
+
+        let report = CoverageReport::new(&dump, CoverageFilter::default());
+        assert_eq!(report.totals.members.total, 2);
+        assert_eq!(report.totals.members.described, 1);
+        assert_eq!(
+            report
+                .totals
+                .members
+                .sources
+                .get(&ContentSource::Supplemental),
+            Some(&1)
+        );
+        assert_eq!(report.totals.members.heuristic_only, 1);
+        assert_eq!(report.totals.members.missing(), 1);
+        assert_eq!(
+            render_sources_text(&report.totals.members),
+            "Dumpling Supplemental: 1, Dumpling Heuristics only (not counted): 1"
+        );
+        assert_eq!(report.totals.parameters.total, 1);
+        assert_eq!(report.totals.enum_items.total, 1);
+
+        let report = CoverageReport::new(
+            &dump,
+            CoverageFilter {
+                exclude_deprecated: true,
+                exclude_hidden: false,
+            },
+        );
+        assert_eq!(report.totals.members.total, 1);
+        assert_eq!(report.totals.members.percent(), 100.0);
+    }
+}
@@ -18,7 +18,6 @@ pub struct ModularBlock {
 #[derive(Debug, Deserialize)]
 pub struct ClassSection {
     pub current_class: Vec<Class>,
-    pub inherited_class: Vec<Class>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,7 +29,6 @@ pub struct Class {
 
 #[derive(Debug, Deserialize)]
 pub struct Property {
-    pub title: String,
     pub display_title: String,
     pub description: Option<String>,
 }
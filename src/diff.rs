@@ -0,0 +1,758 @@
+//! Compares two JSON API dumps and reports what changed between them.
+//!
+//! Changes are reported per item (class, member, enum, or enum item) using the
+//! same dotted targets as supplemental content, like `Part.Size` or
+//! `Enum.Material.Plastic`.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use serde_derive::Serialize;
+
+use crate::dump::{
+    Dump, DumpClass, DumpClassMember, DumpEnum, DumpEnumItem, DumpFunctionParameter,
+    DumpReturnType, DumpTags, DumpType,
+};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DumpDiff {
+    pub changes: Vec<Change>,
+}
+
+impl DumpDiff {
+    pub fn new(old: &Dump, new: &Dump) -> DumpDiff {
+        let mut changes = Vec::new();
+
+        let old_classes: HashMap<&str, &DumpClass> = old
+            .classes
+            .iter()
+            .map(|class| (class.name.as_str(), class))
+            .collect();
+
+        let new_classes: HashMap<&str, &DumpClass> = new
+            .classes
+            .iter()
+            .map(|class| (class.name.as_str(), class))
+            .collect();
+
+        for class in &new.classes {
+            match old_classes.get(class.name.as_str()) {
+                Some(old_class) => diff_classes(old_class, class, &mut changes),
+                None => changes.push(Change::added(&class.name, ItemKind::Class)),
+            }
+        }
+
+        for class in &old.classes {
+            if !new_classes.contains_key(class.name.as_str()) {
+                changes.push(Change::removed(&class.name, ItemKind::Class));
+            }
+        }
+
+        let old_enums: HashMap<&str, &DumpEnum> = old
+            .enums
+            .iter()
+            .map(|item| (item.name.as_str(), item))
+            .collect();
+
+        let new_enums: HashMap<&str, &DumpEnum> = new
+            .enums
+            .iter()
+            .map(|item| (item.name.as_str(), item))
+            .collect();
+
+        for dump_enum in &new.enums {
+            let target = format!("Enum.{}", dump_enum.name);
+
+            match old_enums.get(dump_enum.name.as_str()) {
+                Some(old_enum) => diff_enums(&target, old_enum, dump_enum, &mut changes),
+                None => changes.push(Change::added(&target, ItemKind::Enum)),
+            }
+        }
+
+        for dump_enum in &old.enums {
+            if !new_enums.contains_key(dump_enum.name.as_str()) {
+                let target = format!("Enum.{}", dump_enum.name);
+                changes.push(Change::removed(&target, ItemKind::Enum));
+            }
+        }
+
+        DumpDiff { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Change {
+    pub target: String,
+    pub item: ItemKind,
+
+    #[serde(flatten)]
+    pub kind: ChangeKind,
+}
+
+impl Change {
+    fn added(target: &str, item: ItemKind) -> Change {
+        Change {
+            target: target.to_owned(),
+            item,
+            kind: ChangeKind::Added,
+        }
+    }
+
+    fn removed(target: &str, item: ItemKind) -> Change {
+        Change {
+            target: target.to_owned(),
+            item,
+            kind: ChangeKind::Removed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ItemKind {
+    Class,
+    Property,
+    Function,
+    Event,
+    Callback,
+    UnknownMember,
+    Enum,
+    EnumItem,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, output: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ItemKind::Class => write!(output, "class"),
+            ItemKind::Property => write!(output, "property"),
+            ItemKind::Function => write!(output, "function"),
+            ItemKind::Event => write!(output, "event"),
+            ItemKind::Callback => write!(output, "callback"),
+            ItemKind::UnknownMember => write!(output, "member"),
+            ItemKind::Enum => write!(output, "enum"),
+            ItemKind::EnumItem => write!(output, "enum item"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "Change", rename_all = "PascalCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    #[serde(rename_all = "PascalCase")]
+    Changed {
+        field: ChangedField,
+        old: String,
+        new: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ChangedField {
+    Superclass,
+    ValueType,
+    Parameters,
+    ReturnType,
+    Security,
+    Tags,
+    Value,
+}
+
+impl fmt::Display for ChangedField {
+    fn fmt(&self, output: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChangedField::Superclass => write!(output, "superclass"),
+            ChangedField::ValueType => write!(output, "value type"),
+            ChangedField::Parameters => write!(output, "parameters"),
+            ChangedField::ReturnType => write!(output, "return type"),
+            ChangedField::Security => write!(output, "security"),
+            ChangedField::Tags => write!(output, "tags"),
+            ChangedField::Value => write!(output, "value"),
+        }
+    }
+}
+
+fn push_if_changed(
+    changes: &mut Vec<Change>,
+    target: &str,
+    item: ItemKind,
+    field: ChangedField,
+    old: Option<String>,
+    new: Option<String>,
+) {
+    if old != new {
+        changes.push(Change {
+            target: target.to_owned(),
+            item,
+            kind: ChangeKind::Changed {
+                field,
+                old: old.unwrap_or_default(),
+                new: new.unwrap_or_default(),
+            },
+        });
+    }
+}
+
+fn diff_classes(old: &DumpClass, new: &DumpClass, changes: &mut Vec<Change>) {
+    push_if_changed(
+        changes,
+        &new.name,
+        ItemKind::Class,
+        ChangedField::Superclass,
+        old.superclass.clone(),
+        new.superclass.clone(),
+    );
+
+    push_if_changed(
+        changes,
+        &new.name,
+        ItemKind::Class,
+        ChangedField::Tags,
+        Some(render_tags(&old.tags)),
+        Some(render_tags(&new.tags)),
+    );
+
+    let old_members: HashMap<&str, &DumpClassMember> = old
+        .members
+        .iter()
+        .map(|member| (member.get_name(), member))
+        .collect();
+
+    let new_members: HashMap<&str, &DumpClassMember> = new
+        .members
+        .iter()
+        .map(|member| (member.get_name(), member))
+        .collect();
+
+    for member in &new.members {
+        let target = format!("{}.{}", new.name, member.get_name());
+        let signature = MemberSignature::new(member);
+
+        match old_members.get(member.get_name()) {
+            Some(old_member) => {
+                let old_signature = MemberSignature::new(old_member);
+
+                if old_signature.kind != signature.kind {
+                    changes.push(Change::removed(&target, old_signature.kind));
+                    changes.push(Change::added(&target, signature.kind));
+                } else {
+                    old_signature.diff(signature, &target, changes);
+                }
+            }
+            None => changes.push(Change::added(&target, signature.kind)),
+        }
+    }
+
+    for member in &old.members {
+        if !new_members.contains_key(member.get_name()) {
+            let target = format!("{}.{}", old.name, member.get_name());
+            changes.push(Change::removed(&target, MemberSignature::new(member).kind));
+        }
+    }
+}
+
+fn diff_enums(target: &str, old: &DumpEnum, new: &DumpEnum, changes: &mut Vec<Change>) {
+    push_if_changed(
+        changes,
+        target,
+        ItemKind::Enum,
+        ChangedField::Tags,
+        Some(render_tags(&old.tags)),
+        Some(render_tags(&new.tags)),
+    );
+
+    let old_items: HashMap<&str, &DumpEnumItem> = old
+        .items
+        .iter()
+        .map(|item| (item.name.as_str(), item))
+        .collect();
+
+    let new_items: HashMap<&str, &DumpEnumItem> = new
+        .items
+        .iter()
+        .map(|item| (item.name.as_str(), item))
+        .collect();
+
+    for item in &new.items {
+        let item_target = format!("{}.{}", target, item.name);
+
+        match old_items.get(item.name.as_str()) {
+            Some(old_item) => {
+                push_if_changed(
+                    changes,
+                    &item_target,
+                    ItemKind::EnumItem,
+                    ChangedField::Value,
+                    Some(old_item.value.to_string()),
+                    Some(item.value.to_string()),
+                );
+                push_if_changed(
+                    changes,
+                    &item_target,
+                    ItemKind::EnumItem,
+                    ChangedField::Tags,
+                    Some(render_tags(&old_item.tags)),
+                    Some(render_tags(&item.tags)),
+                );
+            }
+            None => changes.push(Change::added(&item_target, ItemKind::EnumItem)),
+        }
+    }
+
+    for item in &old.items {
+        if !new_items.contains_key(item.name.as_str()) {
+            let item_target = format!("{}.{}", target, item.name);
+            changes.push(Change::removed(&item_target, ItemKind::EnumItem));
+        }
+    }
+}
+
+/// The comparable parts of a member, rendered as strings so that differences
+/// can be reported the same way no matter which kind of member changed.
+struct MemberSignature {
+    kind: ItemKind,
+    value_type: Option<String>,
+    parameters: Option<String>,
+    return_type: Option<String>,
+    security: String,
+    tags: String,
+}
+
+impl MemberSignature {
+    fn new(member: &DumpClassMember) -> MemberSignature {
+        match member {
+            DumpClassMember::Property(property) => {
+                let mut security = property.security.iter().collect::<Vec<_>>();
+                security.sort();
+
+                MemberSignature {
+                    kind: ItemKind::Property,
+                    value_type: Some(property.value_type.simple_name()),
+                    parameters: None,
+                    return_type: None,
+                    security: security
+                        .iter()
+                        .map(|(context, level)| format!("{}: {}", context, level))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    tags: render_tags(&property.tags),
+                }
+            }
+            DumpClassMember::Function(function) => MemberSignature {
+                kind: ItemKind::Function,
+                value_type: None,
+                parameters: Some(render_parameters(&function.parameters)),
+                return_type: Some(render_return_type(&function.return_type)),
+                security: function.security.clone(),
+                tags: render_tags(&function.tags),
+            },
+            DumpClassMember::Event(event) => MemberSignature {
+                kind: ItemKind::Event,
+                value_type: None,
+                parameters: Some(render_parameters(&event.parameters)),
+                return_type: None,
+                security: event.security.clone(),
+                tags: render_tags(&event.tags),
+            },
+            DumpClassMember::Callback(callback) => MemberSignature {
+                kind: ItemKind::Callback,
+                value_type: None,
+                parameters: Some(render_parameters(&callback.parameters)),
+                return_type: Some(render_return_type(&callback.return_type)),
+                security: callback.security.clone(),
+                tags: render_tags(&callback.tags),
+            },
+            DumpClassMember::Unknown(unknown) => MemberSignature {
+                kind: ItemKind::UnknownMember,
+                value_type: None,
+                parameters: None,
+                return_type: None,
+                security: String::new(),
+                tags: render_tags(&unknown.tags),
+            },
+        }
+    }
+
+    fn diff(self, new: MemberSignature, target: &str, changes: &mut Vec<Change>) {
+        let kind = new.kind;
+
+        push_if_changed(
+            changes,
+            target,
+            kind,
+            ChangedField::ValueType,
+            self.value_type,
+            new.value_type,
+        );
+        push_if_changed(
+            changes,
+            target,
+            kind,
+            ChangedField::Parameters,
+            self.parameters,
+            new.parameters,
+        );
+        push_if_changed(
+            changes,
+            target,
+            kind,
+            ChangedField::ReturnType,
+            self.return_type,
+            new.return_type,
+        );
+        push_if_changed(
+            changes,
+            target,
+            kind,
+            ChangedField::Security,
+            Some(self.security),
+            Some(new.security),
+        );
+        push_if_changed(
+            changes,
+            target,
+            kind,
+            ChangedField::Tags,
+            Some(self.tags),
+            Some(new.tags),
+        );
+    }
+}
+
+/// Renders tags in sorted order, since the dump's order doesn't mean anything
+/// and reordered tags shouldn't be reported as a change.
+fn render_tags(tags: &DumpTags) -> String {
+    let mut tags: Vec<_> = tags.iter().map(ToString::to_string).collect();
+    tags.sort();
+
+    format!("[{}]", tags.join(", "))
+}
+
+pub fn render_parameters(parameters: &[DumpFunctionParameter]) -> String {
+    format!(
+        "({})",
+        parameters
+            .iter()
+            .map(|param| format!("{}: {}", param.name, param.kind.simple_name()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+pub fn render_return_type(return_type: &DumpReturnType) -> String {
+    match return_type {
+        DumpReturnType::Single(single) => single.simple_name(),
+        DumpReturnType::Multiple(multiple) => format!(
+            "({})",
+            multiple
+                .iter()
+                .map(DumpType::simple_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Writes the diff as plain text, one change per line.
+pub fn emit_text(diff: &DumpDiff, output: &mut String) -> fmt::Result {
+    if diff.is_empty() {
+        return writeln!(output, "No changes.");
+    }
+
+    for change in &diff.changes {
+        match &change.kind {
+            ChangeKind::Added => writeln!(output, "Added {} {}", change.item, change.target)?,
+            ChangeKind::Removed => writeln!(output, "Removed {} {}", change.item, change.target)?,
+            ChangeKind::Changed { field, old, new } => writeln!(
+                output,
+                "Changed {} of {} {}: {} -> {}",
+                field, change.item, change.target, old, new
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the diff as a Markdown document, grouped by the kind of change.
+pub fn emit_markdown(diff: &DumpDiff, output: &mut String) -> fmt::Result {
+    writeln!(output, "# API Changes")?;
+
+    if diff.is_empty() {
+        writeln!(output)?;
+        return writeln!(output, "No changes.");
+    }
+
+    let added = diff
+        .changes
+        .iter()
+        .filter(|change| matches!(change.kind, ChangeKind::Added));
+    emit_markdown_section("Added", added, output)?;
+
+    let removed = diff
+        .changes
+        .iter()
+        .filter(|change| matches!(change.kind, ChangeKind::Removed));
+    emit_markdown_section("Removed", removed, output)?;
+
+    let changed = diff
+        .changes
+        .iter()
+        .filter(|change| matches!(change.kind, ChangeKind::Changed { .. }));
+    emit_markdown_section("Changed", changed, output)
+}
+
+fn emit_markdown_section<'a>(
+    title: &str,
+    changes: impl Iterator<Item = &'a Change>,
+    output: &mut String,
+) -> fmt::Result {
+    let mut changes = changes.peekable();
+
+    if changes.peek().is_none() {
+        return Ok(());
+    }
+
+    writeln!(output)?;
+    writeln!(output, "## {}", title)?;
+    writeln!(output)?;
+
+    for change in changes {
+        match &change.kind {
+            ChangeKind::Changed { field, old, new } => writeln!(
+                output,
+                "* `{}` ({}): {} changed from `{}` to `{}`",
+                change.target, change.item, field, old, new
+            )?,
+            _ => writeln!(output, "* `{}` ({})", change.target, change.item)?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static OLD: &str = r#"{
+        "Version": 1,
+        "Classes": [
+            {
+                "Name": "Part",
+                "Superclass": "BasePart",
+                "Members": [
+                    {
+                        "MemberType": "Property",
+                        "Name": "Shape",
+                        "ValueType": { "Name": "PartType", "Category": "Enum" },
+                        "Security": { "Read": "None", "Write": "None" },
+                        "Category": "Part"
+                    },
+                    {
+                        "MemberType": "Function",
+                        "Name": "Resize",
+                        "Parameters": [],
+                        "ReturnType": { "Name": "void", "Category": "Primitive" },
+                        "Security": "None"
+                    }
+                ]
+            },
+            { "Name": "Hopper", "Superclass": "Instance", "Members": [] }
+        ],
+        "Enums": [
+            {
+                "Name": "PartType",
+                "Items": [
+                    { "Name": "Ball", "Value": 0 },
+                    { "Name": "Block", "Value": 1 }
+                ]
+            }
+        ]
+    }"#;
+
+    static NEW: &str = r#"{
+        "Version": 1,
+        "Classes": [
+            {
+                "Name": "Part",
+                "Superclass": "BasePart",
+                "Members": [
+                    {
+                        "MemberType": "Property",
+                        "Name": "Shape",
+                        "ValueType": { "Name": "PartType", "Category": "Enum" },
+                        "Security": { "Read": "None", "Write": "PluginSecurity" },
+                        "Category": "Part"
+                    },
+                    {
+                        "MemberType": "Function",
+                        "Name": "Resize",
+                        "Parameters": [
+                            { "Name": "normalId", "Type": { "Name": "NormalId", "Category": "Enum" } }
+                        ],
+                        "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                        "Security": "None",
+                        "Tags": ["Yields"]
+                    },
+                    {
+                        "MemberType": "Event",
+                        "Name": "Touched",
+                        "Parameters": [],
+                        "Security": "None"
+                    }
+                ]
+            }
+        ],
+        "Enums": [
+            {
+                "Name": "PartType",
+                "Items": [
+                    { "Name": "Ball", "Value": 0 },
+                    { "Name": "Block", "Value": 2 },
+                    { "Name": "Cylinder", "Value": 3 }
+                ]
+            }
+        ]
+    }"#;
+
+    fn has_change(diff: &DumpDiff, target: &str, predicate: impl Fn(&ChangeKind) -> bool) -> bool {
+        diff.changes
+            .iter()
+            .any(|change| change.target == target && predicate(&change.kind))
+    }
+
+    fn has_field_change(diff: &DumpDiff, target: &str, expected: ChangedField) -> bool {
+        has_change(diff, target, |kind| match kind {
+            ChangeKind::Changed { field, .. } => *field == expected,
+            _ => false,
+        })
+    }
+
+    #[test]
+    fn detect_changes() {
+        let old: Dump = serde_json::from_str(OLD).unwrap();
+        let new: Dump = serde_json::from_str(NEW).unwrap();
+        let diff = DumpDiff::new(&old, &new);
+
+        assert!(has_change(&diff, "Hopper", |kind| matches!(
+            kind,
+            ChangeKind::Removed
+        )));
+        assert!(has_change(&diff, "Part.Touched", |kind| matches!(
+            kind,
+            ChangeKind::Added
+        )));
+        assert!(has_change(
+            &diff,
+            "Enum.PartType.Cylinder",
+            |kind| matches!(kind, ChangeKind::Added)
+        ));
+
+        assert!(has_field_change(
+            &diff,
+            "Part.Shape",
+            ChangedField::Security
+        ));
+        assert!(has_field_change(
+            &diff,
+            "Part.Resize",
+            ChangedField::Parameters
+        ));
+        assert!(has_field_change(
+            &diff,
+            "Part.Resize",
+            ChangedField::ReturnType
+        ));
+        assert!(has_field_change(&diff, "Part.Resize", ChangedField::Tags));
+        assert!(has_field_change(
+            &diff,
+            "Enum.PartType.Block",
+            ChangedField::Value
+        ));
+
+        assert_eq!(diff.changes.len(), 8);
+    }
+
+    #[test]
+    fn identical_dumps() {
+        let old: Dump = serde_json::from_str(OLD).unwrap();
+        let new: Dump = serde_json::from_str(OLD).unwrap();
+        let diff = DumpDiff::new(&old, &new);
+
+        assert!(diff.is_empty());
+
+        let mut output = String::new();
+        emit_text(&diff, &mut output).unwrap();
+        assert_eq!(output, "No changes.\n");
+    }
+
+    #[test]
+    fn tags_and_type_categories() {
+        let old: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Tags": ["NotCreatable", "Deprecated"],
+                    "Members": [{
+                        "MemberType": "Property",
+                        "Name": "Material",
+                        "ValueType": { "Name": "Material", "Category": "Class" },
+                        "Security": { "Read": "None", "Write": "None" },
+                        "Category": "Part"
+                    }]
+                }],
+                "Enums": [{ "Name": "Material", "Items": [{ "Name": "Plastic", "Value": 256 }] }]
+            }"#,
+        )
+        .unwrap();
+        let new: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Tags": ["Deprecated", "NotCreatable"],
+                    "Members": [{
+                        "MemberType": "Property",
+                        "Name": "Material",
+                        "ValueType": { "Name": "Material", "Category": "Enum" },
+                        "Security": { "Read": "None", "Write": "None" },
+                        "Category": "Part"
+                    }]
+                }],
+                "Enums": [{
+                    "Name": "Material",
+                    "Tags": ["Deprecated"],
+                    "Items": [{ "Name": "Plastic", "Value": 256, "Tags": ["Deprecated"] }]
+                }]
+            }"#,
+        )
+        .unwrap();
+        let diff = DumpDiff::new(&old, &new);
+
+        // Reordered tags aren't a change.
+        assert!(!has_field_change(&diff, "Part", ChangedField::Tags));
+
+        assert!(has_field_change(
+            &diff,
+            "Part.Material",
+            ChangedField::ValueType
+        ));
+        assert!(has_field_change(&diff, "Enum.Material", ChangedField::Tags));
+        assert!(has_field_change(
+            &diff,
+            "Enum.Material.Plastic",
+            ChangedField::Tags
+        ));
+        assert_eq!(diff.changes.len(), 3);
+    }
+}
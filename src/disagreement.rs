@@ -0,0 +1,484 @@
+//! Finds items where content sources disagree with the API dump or with each
+//! other, which usually means that content has fallen behind the engine.
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Write},
+};
+
+use serde_derive::Serialize;
+
+use crate::{
+    dump::{Dump, DumpClassMember, DumpReturnType, DumpTags, DumpType},
+    dump_devhub::DevHubData,
+    reflection_metadata::ReflectionMetadata,
+    supplement::SupplementalData,
+};
+
+/// Return types that stand in for "anything", which supplemental content is
+/// expected to narrow down rather than contradict.
+static UNSPECIFIC_RETURN_TYPES: &[&str] = &["Tuple", "Variant"];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DisagreementReport {
+    pub disagreements: Vec<Disagreement>,
+}
+
+impl DisagreementReport {
+    pub fn new(
+        dump: &Dump,
+        metadata: Option<&ReflectionMetadata>,
+        content: &SupplementalData,
+        devhub: Option<&DevHubData>,
+    ) -> DisagreementReport {
+        let mut disagreements = Vec::new();
+
+        find_return_type_disagreements(dump, content, &mut disagreements);
+
+        if let Some(metadata) = metadata {
+            find_deprecation_disagreements(dump, metadata, &mut disagreements);
+        }
+
+        if let Some(devhub) = devhub {
+            find_devhub_disagreements(dump, devhub, &mut disagreements);
+        }
+
+        disagreements.sort_by(|a, b| a.target.cmp(&b.target));
+
+        DisagreementReport { disagreements }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Disagreement {
+    pub target: String,
+
+    #[serde(flatten)]
+    pub kind: DisagreementKind,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "Disagreement", rename_all = "PascalCase")]
+pub enum DisagreementKind {
+    /// Supplemental `ReturnTypes` name different types than the dump does.
+    #[serde(rename_all = "PascalCase")]
+    ReturnType {
+        dump: Vec<String>,
+        supplemental: Vec<String>,
+        location: String,
+    },
+
+    /// The ReflectionMetadata summary says the item is deprecated, but the
+    /// dump doesn't tag it `Deprecated`.
+    #[serde(rename_all = "PascalCase")]
+    UntaggedDeprecation {
+        summary: String,
+        replacement: Option<String>,
+    },
+
+    /// The DevHub has a page for a class that the dump doesn't have.
+    MissingFromDump,
+
+    /// The dump has a class that the DevHub has no page for.
+    MissingFromDevHub,
+}
+
+fn find_return_type_disagreements(
+    dump: &Dump,
+    content: &SupplementalData,
+    disagreements: &mut Vec<Disagreement>,
+) {
+    for class in &dump.classes {
+        for member in &class.members {
+            let return_type = match member {
+                DumpClassMember::Function(function) => &function.return_type,
+                DumpClassMember::Callback(callback) => &callback.return_type,
+                _ => continue,
+            };
+
+            let target = format!("{}.{}", class.name, member.get_name());
+
+            let item = match content.find_item(&target) {
+                Some(item) => item,
+                None => continue,
+            };
+
+            let supplemental = match &item.metadata.return_types {
+                Some(supplemental) => supplemental,
+                None => continue,
+            };
+
+            let dump_names = return_type_names(return_type);
+
+            let unspecific = dump_names
+                .iter()
+                .all(|name| UNSPECIFIC_RETURN_TYPES.contains(&name.as_str()));
+
+            if !unspecific && dump_names != *supplemental {
+                disagreements.push(Disagreement {
+                    target,
+                    kind: DisagreementKind::ReturnType {
+                        dump: dump_names,
+                        supplemental: supplemental.clone(),
+                        location: item.location.to_string(),
+                    },
+                });
+            }
+        }
+    }
+}
+
+/// Names the return types the way supplemental content does, so that enums
+/// are compared as `Enum.Material` instead of `Material`.
+fn return_type_names(return_type: &DumpReturnType) -> Vec<String> {
+    return_type
+        .types()
+        .iter()
+        .map(DumpType::simple_name)
+        .collect()
+}
+
+fn find_deprecation_disagreements(
+    dump: &Dump,
+    metadata: &ReflectionMetadata,
+    disagreements: &mut Vec<Disagreement>,
+) {
+    let mut check = |target: String, tags: &DumpTags, summary: &str| {
+        if mentions_deprecation(summary) && !tags.contains("Deprecated") {
+            disagreements.push(Disagreement {
+                target,
+                kind: DisagreementKind::UntaggedDeprecation {
+                    summary: summary.to_owned(),
+                    replacement: find_replacement(summary),
+                },
+            });
+        }
+    };
+
+    for class in &dump.classes {
+        let metadata_class = match metadata.classes.get(&class.name) {
+            Some(metadata_class) => metadata_class,
+            None => continue,
+        };
+
+        check(class.name.clone(), &class.tags, &metadata_class.summary);
+
+        for member in &class.members {
+            if let Some(metadata_member) = metadata_class.members.get(member.get_name()) {
+                check(
+                    format!("{}.{}", class.name, member.get_name()),
+                    member.tags(),
+                    &metadata_member.summary,
+                );
+            }
+        }
+    }
+
+    for dump_enum in &dump.enums {
+        let metadata_enum = match metadata.enums.get(&dump_enum.name) {
+            Some(metadata_enum) => metadata_enum,
+            None => continue,
+        };
+
+        let target = format!("Enum.{}", dump_enum.name);
+        check(target.clone(), &dump_enum.tags, &metadata_enum.summary);
+
+        for item in &dump_enum.items {
+            if let Some(metadata_item) = metadata_enum.items.get(&item.name) {
+                check(
+                    format!("{}.{}", target, item.name),
+                    &item.tags,
+                    &metadata_item.summary,
+                );
+            }
+        }
+    }
+}
+
+fn mentions_deprecation(summary: &str) -> bool {
+    let summary = summary.to_lowercase();
+
+    summary.contains("deprecated") || summary.contains("superseded")
+}
+
+/// Picks out the name of the item a summary points readers to instead, as in
+/// "Deprecated. Use `Resize` instead." or "Deprecated in favor of Resize".
+fn find_replacement(summary: &str) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets the same as in the original.
+    let lowercase = summary.to_ascii_lowercase();
+
+    for phrase in &["use ", "in favor of ", "in favour of ", "superseded by "] {
+        // Phrases only count at the start of a word, so the "use" in
+        // "because" doesn't.
+        let starts_word = |index: usize| {
+            !lowercase[..index]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric)
+        };
+
+        for (index, _) in lowercase
+            .match_indices(phrase)
+            .filter(|(index, _)| starts_word(*index))
+        {
+            let name: String = summary[index + phrase.len()..]
+                .trim_start_matches('`')
+                .chars()
+                .take_while(|&char| char.is_alphanumeric() || char == '_' || char == '.')
+                .collect();
+            let name = name.trim_end_matches('.');
+
+            if name.starts_with(char::is_uppercase) {
+                return Some(name.to_owned());
+            }
+        }
+    }
+
+    None
+}
+
+fn find_devhub_disagreements(
+    dump: &Dump,
+    devhub: &DevHubData,
+    disagreements: &mut Vec<Disagreement>,
+) {
+    let dump_classes: BTreeSet<&str> = dump
+        .classes
+        .iter()
+        .map(|class| class.name.as_str())
+        .collect();
+
+    for name in devhub.classes.keys() {
+        if !dump_classes.contains(name.as_str()) {
+            disagreements.push(Disagreement {
+                target: name.clone(),
+                kind: DisagreementKind::MissingFromDump,
+            });
+        }
+    }
+
+    for name in dump_classes {
+        if !devhub.classes.contains_key(name) {
+            disagreements.push(Disagreement {
+                target: name.to_owned(),
+                kind: DisagreementKind::MissingFromDevHub,
+            });
+        }
+    }
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}: ", self.target)?;
+
+        match &self.kind {
+            DisagreementKind::ReturnType {
+                dump,
+                supplemental,
+                location,
+            } => write!(
+                formatter,
+                "supplemental return types `{}` ({}) contradict the dump's `{}`",
+                supplemental.join(", "),
+                location,
+                dump.join(", ")
+            ),
+            DisagreementKind::UntaggedDeprecation { replacement, .. } => {
+                write!(formatter, "ReflectionMetadata says this is deprecated")?;
+
+                if let Some(replacement) = replacement {
+                    write!(formatter, " in favor of `{}`", replacement)?;
+                }
+
+                write!(formatter, ", but the dump doesn't tag it")
+            }
+            DisagreementKind::MissingFromDump => {
+                write!(
+                    formatter,
+                    "the DevHub has a page for this class, but the dump doesn't"
+                )
+            }
+            DisagreementKind::MissingFromDevHub => {
+                write!(
+                    formatter,
+                    "the dump has this class, but the DevHub has no page for it"
+                )
+            }
+        }
+    }
+}
+
+/// Writes the report as plain text, one disagreement per line.
+pub fn emit_text(report: &DisagreementReport, output: &mut String) -> fmt::Result {
+    for disagreement in &report.disagreements {
+        writeln!(output, "{}", disagreement)?;
+    }
+
+    writeln!(
+        output,
+        "{} disagreement{}",
+        report.disagreements.len(),
+        if report.disagreements.len() == 1 {
+            ""
+        } else {
+            "s"
+        }
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::{collections::HashMap, fs};
+
+    use crate::{
+        dump_devhub::DevHubClass,
+        reflection_metadata::{ReflectionMetadataClass, ReflectionMetadataMember},
+    };
+
+    #[test]
+    fn find_disagreements() {
+        let dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Members": [
+                        {
+                            "MemberType": "Function",
+                            "Name": "Resize",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "GetTouchingParts",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "Objects", "Category": "Group" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "getTouchingParts",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "Objects", "Category": "Group" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "GetMaterial",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "Material", "Category": "Enum" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "resize",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                            "Security": "None"
+                        }
+                    ]
+                }],
+                "Enums": []
+            }"#,
+        )
+        .unwrap();
+
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(
+            directory.path().join("Part.md"),
+            "+++\nTarget = \"Part.Resize\"\nReturnTypes = [\"void\"]\n+++\nResizes.\n\n\
+             +++\nTarget = \"Part.GetTouchingParts\"\nReturnTypes = [\"Objects\"]\n+++\nParts.\n\n\
+             +++\nTarget = \"Part.GetMaterial\"\nReturnTypes = [\"Enum.Material\"]\n+++\nThe material.\n",
+        )
+        .unwrap();
+        let content = SupplementalData::read_from_path(directory.path()).unwrap();
+
+        let mut members = HashMap::new();
+        members.insert(
+            "resize".to_owned(),
+            ReflectionMetadataMember {
+                name: "resize".to_owned(),
+                summary: "Deprecated. Use `Resize` instead.".to_owned(),
+                attributes: Default::default(),
+            },
+        );
+        members.insert(
+            "getTouchingParts".to_owned(),
+            ReflectionMetadataMember {
+                name: "getTouchingParts".to_owned(),
+                summary: "Deprecated because Foo was removed.".to_owned(),
+                attributes: Default::default(),
+            },
+        );
+        let mut classes = HashMap::new();
+        classes.insert(
+            "Part".to_owned(),
+            ReflectionMetadataClass {
+                name: "Part".to_owned(),
+                summary: "A part.".to_owned(),
+                attributes: Default::default(),
+                members,
+            },
+        );
+        let metadata = ReflectionMetadata {
+            classes,
+            enums: HashMap::new(),
+        };
+
+        let mut devhub_classes = HashMap::new();
+        devhub_classes.insert(
+            "Hopper".to_owned(),
+            DevHubClass {
+                name: "Hopper".to_owned(),
+                description: String::new(),
+                properties: Vec::new(),
+            },
+        );
+        let devhub = DevHubData {
+            classes: devhub_classes,
+        };
+
+        let report = DisagreementReport::new(&dump, Some(&metadata), &content, Some(&devhub));
+        let found: Vec<_> = report
+            .disagreements
+            .iter()
+            .map(|disagreement| (disagreement.target.as_str(), &disagreement.kind))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("Hopper", &DisagreementKind::MissingFromDump),
+                ("Part", &DisagreementKind::MissingFromDevHub),
+                (
+                    "Part.Resize",
+                    &DisagreementKind::ReturnType {
+                        dump: vec!["bool".to_owned()],
+                        supplemental: vec!["void".to_owned()],
+                        location: format!("{}:1", directory.path().join("Part.md").display()),
+                    }
+                ),
+                (
+                    "Part.getTouchingParts",
+                    &DisagreementKind::UntaggedDeprecation {
+                        summary: "Deprecated because Foo was removed.".to_owned(),
+                        replacement: None,
+                    }
+                ),
+                (
+                    "Part.resize",
+                    &DisagreementKind::UntaggedDeprecation {
+                        summary: "Deprecated. Use `Resize` instead.".to_owned(),
+                        replacement: Some("Resize".to_owned()),
+                    }
+                ),
+            ]
+        );
+    }
+}
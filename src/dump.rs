@@ -1,13 +1,60 @@
-use std::{collections::BTreeSet, collections::HashMap, fmt, fs, io, path::Path, process::Command};
+//! The JSON API dump, which lists every class, member, and enum in the engine,
+//! and the data Dumpling attaches to it.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt, fs, io,
+    path::Path,
+    process::{Command, ExitStatus},
+};
 
 use roblox_install::RobloxStudio;
+use serde::{de, Deserializer, Serializer};
 use serde_derive::{Deserialize, Serialize};
 
+use crate::{
+    error::fmt_roblox_install_error,
+    reflection_metadata::{
+        ReflectionMetadataClassAttributes, ReflectionMetadataEnumAttributes,
+        ReflectionMetadataMemberAttributes,
+    },
+};
+
+/// The newest JSON API dump format version that Dumpling understands.
+pub const CURRENT_DUMP_VERSION: u64 = 1;
+
 #[derive(Debug)]
 pub enum DumpReadError {
     InvalidJson(serde_json::Error),
     IoError(io::Error),
     RobloxInstall(roblox_install::Error),
+    UnsupportedVersion(u64),
+
+    /// Roblox Studio didn't succeed at generating a dump.
+    StudioFailed(ExitStatus),
+}
+
+impl fmt::Display for DumpReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DumpReadError::InvalidJson(error) => write!(formatter, "invalid JSON: {}", error),
+            DumpReadError::IoError(error) => write!(formatter, "{}", error),
+            DumpReadError::RobloxInstall(error) => fmt_roblox_install_error(error, formatter),
+            DumpReadError::UnsupportedVersion(version) => write!(
+                formatter,
+                "dump version {} is newer than the newest supported version, {}",
+                version, CURRENT_DUMP_VERSION
+            ),
+            DumpReadError::StudioFailed(status) => {
+                write!(
+                    formatter,
+                    "Roblox Studio failed to generate a dump ({})",
+                    status
+                )
+            }
+        }
+    }
 }
 
 impl From<serde_json::Error> for DumpReadError {
@@ -29,14 +76,58 @@ impl From<roblox_install::Error> for DumpReadError {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+#[serde(rename_all = "PascalCase", from = "UnindexedDump")]
 pub struct Dump {
     pub classes: Vec<DumpClass>,
     pub enums: Vec<DumpEnum>,
     pub version: u32,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
+    /// The position of each class in `classes` by name, so lookups don't scan
+    /// the whole list. Built when the dump is deserialized and by `reindex`,
+    /// which must be called whenever `classes` changes.
+    #[serde(skip)]
+    class_indices: HashMap<String, usize>,
+}
+
+/// The serialized form of `Dump`, which gets its class index built as soon as
+/// it's deserialized.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct UnindexedDump {
+    classes: Vec<DumpClass>,
+    enums: Vec<DumpEnum>,
+    version: u32,
+
+    #[serde(flatten)]
+    extra: ExtraFields,
+}
+
+impl From<UnindexedDump> for Dump {
+    fn from(unindexed: UnindexedDump) -> Dump {
+        let mut dump = Dump {
+            classes: unindexed.classes,
+            enums: unindexed.enums,
+            version: unindexed.version,
+            extra: unindexed.extra,
+            class_indices: HashMap::new(),
+        };
+        dump.reindex();
+
+        dump
+    }
 }
 
+/// Fields from the JSON API dump that Dumpling doesn't model yet. These are
+/// passed through untouched so that the megadump stays a superset of the
+/// original dump.
+pub type ExtraFields = BTreeMap<String, serde_json::Value>;
+
 impl Dump {
+    /// Reads the dump at the given path, or has Roblox Studio generate a fresh
+    /// one if there's no path.
     pub fn read(path: Option<&Path>) -> Result<Dump, DumpReadError> {
         match path {
             Some(path) => Dump::read_from_file(path),
@@ -46,12 +137,11 @@ impl Dump {
                 let exe_path = RobloxStudio::locate()?.exe_path();
 
                 let status = Command::new(exe_path)
-                    .args(&["-API", &dump_path.display().to_string()])
-                    .status()
-                    .expect("Failed to spawn Roblox Studio process");
+                    .args(["-API", &dump_path.display().to_string()])
+                    .status()?;
 
                 if !status.success() {
-                    panic!("Roblox Studio exited with a non-zero status code");
+                    return Err(DumpReadError::StudioFailed(status));
                 }
 
                 Dump::read_from_file(&dump_path)
@@ -62,13 +152,269 @@ impl Dump {
     pub fn read_from_file(path: &Path) -> Result<Dump, DumpReadError> {
         let contents = fs::read_to_string(path)?;
 
-        let dump: Dump = serde_json::from_str(&contents)?;
+        Dump::read_from_str(&contents)
+    }
+
+    /// Parses a dump, upgrading dumps from before the `Version` field existed
+    /// and rejecting dumps newer than Dumpling understands.
+    pub fn read_from_str(contents: &str) -> Result<Dump, DumpReadError> {
+        let mut value: serde_json::Value = serde_json::from_str(contents)?;
+
+        match value.get("Version").map(serde_json::Value::as_u64) {
+            Some(Some(version)) if version > CURRENT_DUMP_VERSION => {
+                return Err(DumpReadError::UnsupportedVersion(version));
+            }
+            Some(_) => {}
+            None => upgrade_unversioned_dump(&mut value),
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Rebuilds the index used to look up classes by name. Call this after
+    /// adding, removing, or reordering classes, since lookups trust the index.
+    pub fn reindex(&mut self) {
+        self.class_indices = self
+            .classes
+            .iter()
+            .enumerate()
+            .map(|(index, class)| (class.name.clone(), index))
+            .collect();
+    }
+
+    fn class_index(&self, name: &str) -> Option<usize> {
+        self.class_indices.get(name).copied()
+    }
+
+    pub fn find_class(&self, name: &str) -> Option<&DumpClass> {
+        let index = self.class_index(name)?;
+        self.classes.get(index)
+    }
+
+    pub fn find_class_mut(&mut self, name: &str) -> Option<&mut DumpClass> {
+        let index = self.class_index(name)?;
+        self.classes.get_mut(index)
+    }
+
+    /// Walks up the inheritance chain of the given class, starting with its
+    /// superclass. The class itself is not included.
+    pub fn ancestors<'a>(&'a self, name: &str) -> Ancestors<'a> {
+        let next = self
+            .find_class(name)
+            .and_then(|class| class.superclass.as_deref());
+
+        Ancestors {
+            dump: self,
+            next,
+            remaining: self.classes.len(),
+        }
+    }
+
+    /// Lists the classes that inherit directly from the given class.
+    pub fn subclasses<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a DumpClass> {
+        self.classes
+            .iter()
+            .filter(move |class| class.superclass.as_deref() == Some(name))
+    }
+
+    /// Lists every class that inherits from the given class, directly or
+    /// through other subclasses.
+    pub fn descendants<'a>(&'a self, name: &'a str) -> Vec<&'a DumpClass> {
+        let mut descendants = Vec::new();
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![name];
+
+        while let Some(current) = to_visit.pop() {
+            for subclass in self.subclasses(current) {
+                if visited.insert(subclass.name.as_str()) {
+                    descendants.push(subclass);
+                    to_visit.push(&subclass.name);
+                }
+            }
+        }
+
+        descendants
+    }
+
+    /// Iterates over every member available on the given class, including
+    /// inherited ones, along with the class that declares each member. Members
+    /// declared closer to the class shadow ancestor members of the same name.
+    pub fn all_members<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl Iterator<Item = (&'a DumpClass, &'a DumpClassMember)> {
+        let mut seen = HashSet::new();
+
+        self.find_class(name)
+            .into_iter()
+            .chain(self.ancestors(name))
+            .flat_map(|class| class.members.iter().map(move |member| (class, member)))
+            .filter(move |(_, member)| seen.insert(member.get_name()))
+    }
+
+    /// Fills in `subclasses` and `inherited_members` on every class so that
+    /// consumers of the megadump don't need to walk the class tree themselves.
+    pub fn resolve_inheritance(&mut self) {
+        self.reindex();
+
+        let mut subclasses_by_name: HashMap<&str, Vec<String>> = HashMap::new();
+        for class in &self.classes {
+            if let Some(superclass) = &class.superclass {
+                subclasses_by_name
+                    .entry(superclass.as_str())
+                    .or_default()
+                    .push(class.name.clone());
+            }
+        }
+
+        let mut resolved = Vec::with_capacity(self.classes.len());
+
+        for class in &self.classes {
+            let subclasses = subclasses_by_name
+                .remove(class.name.as_str())
+                .unwrap_or_default();
+
+            let inherited_members = self
+                .all_members(&class.name)
+                .filter(|(declaring_class, _)| declaring_class.name != class.name)
+                .map(|(declaring_class, member)| DumpInheritedMember {
+                    name: member.get_name().to_owned(),
+                    member_type: member.member_type().to_owned(),
+                    declaring_class: declaring_class.name.clone(),
+                })
+                .collect();
+
+            resolved.push((subclasses, inherited_members));
+        }
+
+        for (class, (subclasses, inherited_members)) in self.classes.iter_mut().zip(resolved) {
+            class.subclasses = Some(subclasses);
+            class.inherited_members = Some(inherited_members);
+        }
+    }
+
+    /// Collects every type that supplemental content can refer to, keyed by
+    /// the name content uses for it: every class and enum, plus every type the
+    /// dump itself uses. Enums are named like `Enum.Material`.
+    pub fn named_types(&self) -> HashMap<String, DumpType> {
+        let mut types = HashMap::new();
+
+        let mut add_type = |dump_type: &DumpType| {
+            types
+                .entry(dump_type.simple_name())
+                .or_insert_with(|| dump_type.clone());
+        };
+
+        for class in &self.classes {
+            for member in &class.members {
+                match member {
+                    DumpClassMember::Property(property) => add_type(&property.value_type),
+                    DumpClassMember::Function(function) => {
+                        function
+                            .parameters
+                            .iter()
+                            .for_each(|parameter| add_type(&parameter.kind));
+                        function.return_type.types().iter().for_each(&mut add_type);
+                    }
+                    DumpClassMember::Event(event) => event
+                        .parameters
+                        .iter()
+                        .for_each(|parameter| add_type(&parameter.kind)),
+                    DumpClassMember::Callback(callback) => {
+                        callback
+                            .parameters
+                            .iter()
+                            .for_each(|parameter| add_type(&parameter.kind));
+                        callback.return_type.types().iter().for_each(&mut add_type);
+                    }
+                    DumpClassMember::Unknown(_) => {}
+                }
+            }
+        }
+
+        for class in &self.classes {
+            add_type(&DumpType {
+                name: class.name.clone(),
+                category: "Class".to_owned(),
+            });
+        }
+
+        for dump_enum in &self.enums {
+            add_type(&DumpType {
+                name: dump_enum.name.clone(),
+                category: "Enum".to_owned(),
+            });
+        }
+
+        types
+    }
+}
+
+pub struct Ancestors<'a> {
+    dump: &'a Dump,
+    next: Option<&'a str>,
+
+    /// Guards against malformed dumps with inheritance cycles.
+    remaining: usize,
+}
+
+impl<'a> Iterator for Ancestors<'a> {
+    type Item = &'a DumpClass;
+
+    fn next(&mut self) -> Option<&'a DumpClass> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let class = self.dump.find_class(self.next?)?;
+        self.next = class.superclass.as_deref();
+
+        Some(class)
+    }
+}
+
+/// Dumps from before the format was versioned sometimes leave out the enum
+/// list and describe property security as a single level instead of separate
+/// read and write levels. This rewrites them into the current layout.
+fn upgrade_unversioned_dump(value: &mut serde_json::Value) {
+    let object = match value.as_object_mut() {
+        Some(object) => object,
+        None => return,
+    };
+
+    object.insert("Version".to_owned(), CURRENT_DUMP_VERSION.into());
+    object
+        .entry("Enums")
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+
+    let classes = match object.get_mut("Classes").and_then(|c| c.as_array_mut()) {
+        Some(classes) => classes,
+        None => return,
+    };
+
+    for class in classes {
+        let members = match class.get_mut("Members").and_then(|m| m.as_array_mut()) {
+            Some(members) => members,
+            None => continue,
+        };
+
+        for member in members {
+            if member["MemberType"] != "Property" {
+                continue;
+            }
 
-        Ok(dump)
+            if let Some(level) = member["Security"].as_str().map(str::to_owned) {
+                member["Security"] = serde_json::json!({
+                    "Read": level,
+                    "Write": level,
+                });
+            }
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ContentSource {
     ApiDump,
     ReflectionMetadata,
@@ -97,18 +443,63 @@ pub struct DumpClass {
     pub members: Vec<DumpClassMember>,
 
     #[serde(default)]
-    pub tags: BTreeSet<String>,
+    pub tags: DumpTags,
 
     pub superclass: Option<String>,
 
+    pub memory_category: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
     /// Added by Dumpling
     pub description: Option<String>,
 
     /// Added by Dumpling
     pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling, only for descriptions from supplemental content
+    pub description_origin: Option<DescriptionOrigin>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
+
+    /// Added by Dumpling
+    pub history: Option<DumpItemHistory>,
+
+    /// Added by Dumpling
+    pub reflection_metadata: Option<ReflectionMetadataClassAttributes>,
+
+    /// Added by Dumpling, only for tags added by a content source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_sources: BTreeMap<String, ContentSource>,
+
+    /// Added by Dumpling, only when inheritance is resolved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subclasses: Option<Vec<String>>,
+
+    /// Added by Dumpling, only when inheritance is resolved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherited_members: Option<Vec<DumpInheritedMember>>,
+}
+
+/// A member a class gets from one of its ancestors.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DumpInheritedMember {
+    pub name: String,
+    pub member_type: String,
+    pub declaring_class: String,
 }
 
 impl DumpClass {
+    /// The name of the class that should be used instead of this one, if the
+    /// dump names one.
+    pub fn preferred_replacement(&self) -> Option<&str> {
+        self.tags.preferred_descriptor_name()
+    }
+
     pub fn properties(&self) -> impl Iterator<Item = &DumpClassProperty> {
         self.members.iter().filter_map(|member| match member {
             DumpClassMember::Property(inner) => Some(inner),
@@ -159,66 +550,279 @@ impl DumpClass {
     pub fn has_callbacks(&self) -> bool {
         self.callbacks().next().is_some()
     }
+
+    pub fn unknown_members(&self) -> impl Iterator<Item = &DumpClassUnknownMember> {
+        self.members.iter().filter_map(|member| match member {
+            DumpClassMember::Unknown(inner) => Some(inner),
+            _ => None,
+        })
+    }
+
+    pub fn has_unknown_members(&self) -> bool {
+        self.unknown_members().next().is_some()
+    }
+
+    pub fn add_tag(&mut self, tag: &str, source: ContentSource) {
+        add_tag_from_source(&mut self.tags, &mut self.tag_sources, tag, source);
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(tag = "MemberType")]
+#[derive(Debug)]
 pub enum DumpClassMember {
     Property(DumpClassProperty),
     Function(DumpClassFunction),
     Event(DumpClassEvent),
     Callback(DumpClassCallback),
+    Unknown(DumpClassUnknownMember),
+}
+
+/// Borrowed form of the known member kinds, used to serialize them with their
+/// `MemberType` tag.
+#[derive(Serialize)]
+#[serde(tag = "MemberType")]
+enum TaggedMember<'a> {
+    Property(&'a DumpClassProperty),
+    Function(&'a DumpClassFunction),
+    Event(&'a DumpClassEvent),
+    Callback(&'a DumpClassCallback),
+}
+
+impl serde::Serialize for DumpClassMember {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let tagged = match self {
+            DumpClassMember::Property(inner) => TaggedMember::Property(inner),
+            DumpClassMember::Function(inner) => TaggedMember::Function(inner),
+            DumpClassMember::Event(inner) => TaggedMember::Event(inner),
+            DumpClassMember::Callback(inner) => TaggedMember::Callback(inner),
+            DumpClassMember::Unknown(inner) => {
+                return serde::Serialize::serialize(&inner.raw, serializer)
+            }
+        };
+
+        serde::Serialize::serialize(&tagged, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DumpClassMember {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let raw = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+
+        let member_type = match raw.get("MemberType").and_then(serde_json::Value::as_str) {
+            Some(member_type) => member_type.to_owned(),
+            None => return Err(D::Error::missing_field("MemberType")),
+        };
+
+        fn parse<T: de::DeserializeOwned, E: Error>(mut raw: serde_json::Value) -> Result<T, E> {
+            // The tag is written back out when serializing, so it shouldn't
+            // end up in the member's extra fields.
+            if let Some(object) = raw.as_object_mut() {
+                object.remove("MemberType");
+            }
+
+            serde_json::from_value(raw).map_err(E::custom)
+        }
+
+        Ok(match member_type.as_str() {
+            "Property" => DumpClassMember::Property(parse(raw)?),
+            "Function" => DumpClassMember::Function(parse(raw)?),
+            "Event" => DumpClassMember::Event(parse(raw)?),
+            "Callback" => DumpClassMember::Callback(parse(raw)?),
+            _ => {
+                let name = raw
+                    .get("Name")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .to_owned();
+
+                let tags = raw
+                    .get("Tags")
+                    .map(|tags| serde_json::from_value(tags.clone()))
+                    .transpose()
+                    .map_err(D::Error::custom)?
+                    .unwrap_or_default();
+
+                DumpClassMember::Unknown(DumpClassUnknownMember {
+                    member_type,
+                    name,
+                    tags,
+                    raw,
+                })
+            }
+        })
+    }
 }
 
 impl DumpClassMember {
+    pub fn member_type(&self) -> &str {
+        match self {
+            DumpClassMember::Property(_) => "Property",
+            DumpClassMember::Function(_) => "Function",
+            DumpClassMember::Event(_) => "Event",
+            DumpClassMember::Callback(_) => "Callback",
+            DumpClassMember::Unknown(inner) => inner.member_type.as_str(),
+        }
+    }
+
     pub fn get_name(&self) -> &str {
         match self {
             DumpClassMember::Property(inner) => inner.name.as_str(),
             DumpClassMember::Function(inner) => inner.name.as_str(),
             DumpClassMember::Event(inner) => inner.name.as_str(),
             DumpClassMember::Callback(inner) => inner.name.as_str(),
+            DumpClassMember::Unknown(inner) => inner.name.as_str(),
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            DumpClassMember::Property(inner) => inner.description.as_deref(),
+            DumpClassMember::Function(inner) => inner.description.as_deref(),
+            DumpClassMember::Event(inner) => inner.description.as_deref(),
+            DumpClassMember::Callback(inner) => inner.description.as_deref(),
+            DumpClassMember::Unknown(_) => None,
+        }
+    }
+
+    pub fn description_source(&self) -> Option<ContentSource> {
+        match self {
+            DumpClassMember::Property(inner) => inner.description_source,
+            DumpClassMember::Function(inner) => inner.description_source,
+            DumpClassMember::Event(inner) => inner.description_source,
+            DumpClassMember::Callback(inner) => inner.description_source,
+            DumpClassMember::Unknown(_) => None,
+        }
+    }
+
+    /// The parameters of functions, events, and callbacks. Other members have
+    /// none.
+    pub fn parameters(&self) -> &[DumpFunctionParameter] {
+        match self {
+            DumpClassMember::Function(inner) => &inner.parameters,
+            DumpClassMember::Event(inner) => &inner.parameters,
+            DumpClassMember::Callback(inner) => &inner.parameters,
+            DumpClassMember::Property(_) | DumpClassMember::Unknown(_) => &[],
         }
     }
 
     pub fn set_description(&mut self, description: String, source: ContentSource) {
+        let (current, current_source, candidates) = match self {
+            DumpClassMember::Property(inner) => (
+                &mut inner.description,
+                &mut inner.description_source,
+                &mut inner.description_candidates,
+            ),
+            DumpClassMember::Function(inner) => (
+                &mut inner.description,
+                &mut inner.description_source,
+                &mut inner.description_candidates,
+            ),
+            DumpClassMember::Event(inner) => (
+                &mut inner.description,
+                &mut inner.description_source,
+                &mut inner.description_candidates,
+            ),
+            DumpClassMember::Callback(inner) => (
+                &mut inner.description,
+                &mut inner.description_source,
+                &mut inner.description_candidates,
+            ),
+            DumpClassMember::Unknown(_) => return,
+        };
+
+        candidates.offer(source, &description, true);
+        *current = Some(description);
+        *current_source = Some(source);
+    }
+
+    /// Records a description that was offered for this member but not used.
+    pub fn reject_description(&mut self, description: &str, source: ContentSource) {
+        if let Some(candidates) = self.description_candidates_mut() {
+            candidates.offer(source, description, false);
+        }
+    }
+
+    fn description_candidates_mut(&mut self) -> Option<&mut DescriptionCandidates> {
         match self {
-            DumpClassMember::Property(inner) => {
-                inner.description = Some(description);
-                inner.description_source = Some(source);
-            }
-            DumpClassMember::Function(inner) => {
-                inner.description = Some(description);
-                inner.description_source = Some(source);
-            }
-            DumpClassMember::Event(inner) => {
-                inner.description = Some(description);
-                inner.description_source = Some(source);
-            }
-            DumpClassMember::Callback(inner) => {
-                inner.description = Some(description);
-                inner.description_source = Some(source);
-            }
+            DumpClassMember::Property(inner) => Some(&mut inner.description_candidates),
+            DumpClassMember::Function(inner) => Some(&mut inner.description_candidates),
+            DumpClassMember::Event(inner) => Some(&mut inner.description_candidates),
+            DumpClassMember::Callback(inner) => Some(&mut inner.description_candidates),
+            DumpClassMember::Unknown(_) => None,
+        }
+    }
+
+    pub fn set_history(&mut self, history: Option<DumpItemHistory>) {
+        match self {
+            DumpClassMember::Property(inner) => inner.history = history,
+            DumpClassMember::Function(inner) => inner.history = history,
+            DumpClassMember::Event(inner) => inner.history = history,
+            DumpClassMember::Callback(inner) => inner.history = history,
+            DumpClassMember::Unknown(_) => {}
         }
     }
 
-    pub fn add_tag(&mut self, tag: &str) {
-        let tags = match self {
-            DumpClassMember::Property(inner) => &mut inner.tags,
-            DumpClassMember::Function(inner) => &mut inner.tags,
-            DumpClassMember::Event(inner) => &mut inner.tags,
-            DumpClassMember::Callback(inner) => &mut inner.tags,
+    pub fn set_reflection_metadata(&mut self, attributes: ReflectionMetadataMemberAttributes) {
+        let attributes = Some(attributes);
+
+        match self {
+            DumpClassMember::Property(inner) => inner.reflection_metadata = attributes,
+            DumpClassMember::Function(inner) => inner.reflection_metadata = attributes,
+            DumpClassMember::Event(inner) => inner.reflection_metadata = attributes,
+            DumpClassMember::Callback(inner) => inner.reflection_metadata = attributes,
+            DumpClassMember::Unknown(_) => {}
+        }
+    }
+
+    pub fn tags(&self) -> &DumpTags {
+        match self {
+            DumpClassMember::Property(inner) => &inner.tags,
+            DumpClassMember::Function(inner) => &inner.tags,
+            DumpClassMember::Event(inner) => &inner.tags,
+            DumpClassMember::Callback(inner) => &inner.tags,
+            DumpClassMember::Unknown(inner) => &inner.tags,
+        }
+    }
+
+    pub fn add_tag(&mut self, tag: &str, source: ContentSource) {
+        let (tags, tag_sources) = match self {
+            DumpClassMember::Property(inner) => (&mut inner.tags, &mut inner.tag_sources),
+            DumpClassMember::Function(inner) => (&mut inner.tags, &mut inner.tag_sources),
+            DumpClassMember::Event(inner) => (&mut inner.tags, &mut inner.tag_sources),
+            DumpClassMember::Callback(inner) => (&mut inner.tags, &mut inner.tag_sources),
+
+            // Unknown members are written back out exactly as we read them.
+            DumpClassMember::Unknown(_) => return,
         };
-        tags.insert(tag.to_owned());
+        add_tag_from_source(tags, tag_sources, tag, source);
+    }
+
+    /// The name of the member that should be used instead of this one, if the
+    /// dump names one.
+    pub fn preferred_replacement(&self) -> Option<&str> {
+        self.tags().preferred_descriptor_name()
     }
 }
 
+/// A member with a `MemberType` that Dumpling doesn't know about yet.
+#[derive(Debug)]
+pub struct DumpClassUnknownMember {
+    pub member_type: String,
+    pub name: String,
+    pub tags: DumpTags,
+
+    /// The member's original JSON, which is serialized back out unchanged.
+    pub raw: serde_json::Value,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct DumpClassProperty {
     pub name: String,
 
     #[serde(default)]
-    pub tags: BTreeSet<String>,
+    pub tags: DumpTags,
 
     pub value_type: DumpType,
 
@@ -226,11 +830,35 @@ pub struct DumpClassProperty {
 
     pub category: String,
 
+    pub serialization: Option<DumpPropertySerialization>,
+
+    pub thread_safety: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
     /// Added by Dumpling
     pub description: Option<String>,
 
     /// Added by Dumpling
     pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling, only for descriptions from supplemental content
+    pub description_origin: Option<DescriptionOrigin>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
+
+    /// Added by Dumpling
+    pub history: Option<DumpItemHistory>,
+
+    /// Added by Dumpling
+    pub reflection_metadata: Option<ReflectionMetadataMemberAttributes>,
+
+    /// Added by Dumpling, only for tags added by a content source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_sources: BTreeMap<String, ContentSource>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -239,7 +867,7 @@ pub struct DumpClassFunction {
     pub name: String,
 
     #[serde(default)]
-    pub tags: BTreeSet<String>,
+    pub tags: DumpTags,
 
     pub parameters: Vec<DumpFunctionParameter>,
 
@@ -247,11 +875,33 @@ pub struct DumpClassFunction {
 
     pub security: String,
 
+    pub thread_safety: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
     /// Added by Dumpling
     pub description: Option<String>,
 
     /// Added by Dumpling
     pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling, only for descriptions from supplemental content
+    pub description_origin: Option<DescriptionOrigin>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
+
+    /// Added by Dumpling
+    pub history: Option<DumpItemHistory>,
+
+    /// Added by Dumpling
+    pub reflection_metadata: Option<ReflectionMetadataMemberAttributes>,
+
+    /// Added by Dumpling, only for tags added by a content source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_sources: BTreeMap<String, ContentSource>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -260,17 +910,39 @@ pub struct DumpClassEvent {
     pub name: String,
 
     #[serde(default)]
-    pub tags: BTreeSet<String>,
+    pub tags: DumpTags,
 
     pub parameters: Vec<DumpFunctionParameter>,
 
     pub security: String,
 
+    pub thread_safety: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
     /// Added by Dumpling
     pub description: Option<String>,
 
     /// Added by Dumpling
     pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling, only for descriptions from supplemental content
+    pub description_origin: Option<DescriptionOrigin>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
+
+    /// Added by Dumpling
+    pub history: Option<DumpItemHistory>,
+
+    /// Added by Dumpling
+    pub reflection_metadata: Option<ReflectionMetadataMemberAttributes>,
+
+    /// Added by Dumpling, only for tags added by a content source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_sources: BTreeMap<String, ContentSource>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -279,7 +951,7 @@ pub struct DumpClassCallback {
     pub name: String,
 
     #[serde(default)]
-    pub tags: BTreeSet<String>,
+    pub tags: DumpTags,
 
     pub parameters: Vec<DumpFunctionParameter>,
 
@@ -287,11 +959,33 @@ pub struct DumpClassCallback {
 
     pub security: String,
 
+    pub thread_safety: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
     /// Added by Dumpling
     pub description: Option<String>,
 
     /// Added by Dumpling
     pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling, only for descriptions from supplemental content
+    pub description_origin: Option<DescriptionOrigin>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
+
+    /// Added by Dumpling
+    pub history: Option<DumpItemHistory>,
+
+    /// Added by Dumpling
+    pub reflection_metadata: Option<ReflectionMetadataMemberAttributes>,
+
+    /// Added by Dumpling, only for tags added by a content source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_sources: BTreeMap<String, ContentSource>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -302,25 +996,159 @@ pub struct DumpFunctionParameter {
     #[serde(rename = "Type")]
     pub kind: DumpType,
 
+    pub default: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
+    /// Added by Dumpling
+    pub optional: Option<bool>,
+
     /// Added by Dumpling
     pub description: Option<String>,
 
     /// Added by Dumpling
     pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The tags attached to a class, member, enum, or enum item.
+///
+/// Most tags are plain flags like `"Deprecated"`, but newer dumps also contain
+/// structured tags like `{ "PreferredDescriptorName": "Destroy" }`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DumpTags(Vec<DumpTag>);
+
+impl DumpTags {
+    pub fn iter(&self) -> impl Iterator<Item = &DumpTag> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Tells whether the given plain flag is present.
+    pub fn contains(&self, flag: &str) -> bool {
+        self.0.iter().any(|tag| match tag {
+            DumpTag::Flag(name) => name == flag,
+            DumpTag::Structured(_) => false,
+        })
+    }
+
+    /// Adds a plain flag, if it isn't already present.
+    pub fn insert(&mut self, flag: &str) {
+        if !self.contains(flag) {
+            self.0.push(DumpTag::Flag(flag.to_owned()));
+        }
+    }
+
+    /// Finds the value of a structured tag with the given key.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.iter().find_map(|tag| match tag {
+            DumpTag::Flag(_) => None,
+            DumpTag::Structured(fields) => fields.get(key),
+        })
+    }
+
+    pub fn preferred_descriptor_name(&self) -> Option<&str> {
+        self.get("PreferredDescriptorName")
+            .and_then(serde_json::Value::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DumpTag {
+    Flag(String),
+    Structured(BTreeMap<String, serde_json::Value>),
+}
+
+impl fmt::Display for DumpTag {
+    fn fmt(&self, output: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DumpTag::Flag(name) => write!(output, "{}", name),
+            DumpTag::Structured(fields) => {
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(output, ", ")?;
+                    }
+
+                    // Strings are written without the quotes JSON would add.
+                    let value = match value {
+                        serde_json::Value::String(value) => Cow::Borrowed(value.as_str()),
+                        value => Cow::Owned(value.to_string()),
+                    };
+
+                    write!(output, "{}: {}", key, value)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct DumpType {
     pub name: String,
     pub category: String,
 }
 
+impl DumpType {
+    /// The name supplemental content uses for this type, like `Enum.Material`
+    /// for enums and the plain name for everything else.
+    pub fn simple_name(&self) -> String {
+        if self.category == "Enum" {
+            format!("Enum.{}", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct DumpEnum {
     pub name: String,
     pub items: Vec<DumpEnumItem>,
+
+    #[serde(default)]
+    pub tags: DumpTags,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
+    /// Added by Dumpling
+    pub description: Option<String>,
+
+    /// Added by Dumpling
+    pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling, only for descriptions from supplemental content
+    pub description_origin: Option<DescriptionOrigin>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
+
+    /// Added by Dumpling
+    pub reflection_metadata: Option<ReflectionMetadataEnumAttributes>,
+
+    /// Added by Dumpling, only for tags added by a content source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_sources: BTreeMap<String, ContentSource>,
+}
+
+impl DumpEnum {
+    pub fn add_tag(&mut self, tag: &str, source: ContentSource) {
+        add_tag_from_source(&mut self.tags, &mut self.tag_sources, tag, source);
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -328,6 +1156,155 @@ pub struct DumpEnum {
 pub struct DumpEnumItem {
     pub name: String,
     pub value: u32,
+
+    #[serde(default)]
+    pub tags: DumpTags,
+
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+
+    /// Added by Dumpling
+    pub description: Option<String>,
+
+    /// Added by Dumpling
+    pub description_source: Option<ContentSource>,
+
+    /// Added by Dumpling, only for descriptions from supplemental content
+    pub description_origin: Option<DescriptionOrigin>,
+
+    /// Added by Dumpling
+    #[serde(default)]
+    pub description_candidates: DescriptionCandidates,
+
+    /// Added by Dumpling
+    pub history: Option<DumpItemHistory>,
+
+    /// Added by Dumpling
+    pub reflection_metadata: Option<ReflectionMetadataMemberAttributes>,
+
+    /// Added by Dumpling, only for tags added by a content source
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_sources: BTreeMap<String, ContentSource>,
+}
+
+impl DumpEnumItem {
+    pub fn add_tag(&mut self, tag: &str, source: ContentSource) {
+        add_tag_from_source(&mut self.tags, &mut self.tag_sources, tag, source);
+    }
+}
+
+/// Adds a plain flag on behalf of a content source. The source is only
+/// recorded if the flag wasn't already there, so tags from the dump itself
+/// are never attributed to anything else.
+fn add_tag_from_source(
+    tags: &mut DumpTags,
+    tag_sources: &mut BTreeMap<String, ContentSource>,
+    tag: &str,
+    source: ContentSource,
+) {
+    if !tags.contains(tag) {
+        tags.insert(tag);
+        tag_sources.insert(tag.to_owned(), source);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DumpPropertySerialization {
+    pub can_load: bool,
+    pub can_save: bool,
+}
+
+/// Every description offered for an item by a content source, in the order
+/// they were offered, with the one that was used marked as chosen.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DescriptionCandidates(Vec<DescriptionCandidate>);
+
+impl DescriptionCandidates {
+    /// Records a description offered by a source. If it was chosen, any
+    /// previously chosen description is unmarked.
+    pub fn offer(&mut self, source: ContentSource, description: &str, chosen: bool) {
+        if chosen {
+            for candidate in &mut self.0 {
+                candidate.chosen = false;
+            }
+        }
+
+        self.0.push(DescriptionCandidate {
+            source,
+            description: description.to_owned(),
+            chosen,
+        });
+    }
+
+    /// Records a description that was built on top of the chosen one. Both
+    /// are used, so the previously chosen description stays marked.
+    pub fn offer_combined(&mut self, source: ContentSource, description: &str) {
+        self.0.push(DescriptionCandidate {
+            source,
+            description: description.to_owned(),
+            chosen: true,
+        });
+    }
+
+    /// The descriptions that weren't chosen.
+    pub fn alternatives(&self) -> impl Iterator<Item = &DescriptionCandidate> {
+        self.0.iter().filter(|candidate| !candidate.chosen)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DescriptionCandidate {
+    pub source: ContentSource,
+    pub description: String,
+    pub chosen: bool,
+}
+
+/// Where in the supplemental content a description was written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DescriptionOrigin {
+    /// The path of the file, relative to the content directory and using
+    /// forward slashes.
+    pub path: String,
+
+    /// The 1-based line the description's metadata block starts on.
+    pub start_line: usize,
+
+    /// The 1-based line the description's prose ends on.
+    pub end_line: usize,
+}
+
+/// When an item first and last appeared in a series of historical API dumps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DumpItemHistory {
+    pub first_seen: String,
+    pub last_seen: String,
+
+    /// The dump this item most recently started appearing in, unless it has
+    /// been present since the oldest dump we know about.
+    pub added_in: Option<String>,
+
+    /// The first dump after `last_seen`, if the item disappeared.
+    pub removed_in: Option<String>,
+
+    /// The times the item disappeared and came back later, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gaps: Vec<DumpHistoryGap>,
+}
+
+/// A stretch of dumps an item was missing from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DumpHistoryGap {
+    /// The first dump the item was missing from.
+    pub removed_in: String,
+
+    /// The first dump the item came back in.
+    pub readded_in: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -336,3 +1313,300 @@ pub enum DumpReturnType {
     Single(DumpType),
     Multiple(Vec<DumpType>),
 }
+
+impl DumpReturnType {
+    pub fn types(&self) -> &[DumpType] {
+        match self {
+            DumpReturnType::Single(single) => std::slice::from_ref(single),
+            DumpReturnType::Multiple(multiple) => multiple,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"{
+        "Version": 1,
+        "Classes": [
+            {
+                "Name": "Part",
+                "Superclass": "FormFactorPart",
+                "MemoryCategory": "PhysicsParts",
+                "Members": [
+                    {
+                        "MemberType": "Property",
+                        "Name": "Shape",
+                        "ValueType": { "Name": "PartType", "Category": "Enum" },
+                        "Security": { "Read": "None", "Write": "None" },
+                        "Category": "Part",
+                        "Serialization": { "CanLoad": true, "CanSave": true },
+                        "ThreadSafety": "ReadSafe",
+                        "Capabilities": ["Basic"]
+                    },
+                    {
+                        "MemberType": "Function",
+                        "Name": "Resize",
+                        "Parameters": [
+                            {
+                                "Name": "normalId",
+                                "Type": { "Name": "NormalId", "Category": "Enum" },
+                                "Default": "Top"
+                            }
+                        ],
+                        "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                        "Security": "None",
+                        "ThreadSafety": "Unsafe"
+                    },
+                    {
+                        "MemberType": "Function",
+                        "Name": "resize",
+                        "Parameters": [],
+                        "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                        "Security": "None",
+                        "Tags": [
+                            "Deprecated",
+                            { "PreferredDescriptorName": "Resize" },
+                            { "ThreadSafety": "Unsafe" }
+                        ]
+                    }
+                ]
+            }
+        ],
+        "Enums": [
+            {
+                "Name": "PartType",
+                "Items": [
+                    { "Name": "Ball", "Value": 0, "Tags": ["Deprecated"], "LegacyNames": ["Sphere"] }
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn unknown_member_round_trip() {
+        let source = r#"{
+            "Version": 1,
+            "Classes": [
+                {
+                    "Name": "Part",
+                    "Members": [
+                        {
+                            "MemberType": "Method",
+                            "Name": "Explode",
+                            "Tags": ["Deprecated"],
+                            "Overloads": [{ "Parameters": [] }]
+                        }
+                    ]
+                }
+            ],
+            "Enums": []
+        }"#;
+
+        let dump = Dump::read_from_str(source).unwrap();
+        let member = dump.classes[0].unknown_members().next().unwrap();
+        assert_eq!(member.member_type, "Method");
+        assert_eq!(member.name, "Explode");
+        assert!(member.tags.contains("Deprecated"));
+
+        let output = serde_json::to_value(&dump).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(source).unwrap();
+        assert_eq!(
+            output["Classes"][0]["Members"][0],
+            expected["Classes"][0]["Members"][0]
+        );
+    }
+
+    #[test]
+    fn description_candidates() {
+        let mut dump = Dump::read_from_str(EXAMPLE).unwrap();
+        let member = &mut dump.classes[0].members[1];
+
+        member.set_description("Resizes.".to_owned(), ContentSource::ReflectionMetadata);
+        member.set_description("Grows the part.".to_owned(), ContentSource::Supplemental);
+        member.reject_description("Use Resize.", ContentSource::Heuristic);
+
+        assert_eq!(member.description(), Some("Grows the part."));
+
+        let output = serde_json::to_value(&dump).unwrap();
+        let candidates = &output["Classes"][0]["Members"][1]["DescriptionCandidates"];
+        assert_eq!(candidates.as_array().unwrap().len(), 3);
+        assert_eq!(candidates[0]["Chosen"], false);
+        assert_eq!(candidates[1]["Source"], "Supplemental");
+        assert_eq!(candidates[1]["Chosen"], true);
+
+        let alternatives: Vec<_> = match &dump.classes[0].members[1] {
+            DumpClassMember::Function(function) => function
+                .description_candidates
+                .alternatives()
+                .map(|candidate| candidate.source)
+                .collect(),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            alternatives,
+            vec![ContentSource::ReflectionMetadata, ContentSource::Heuristic]
+        );
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let source = r#"{ "Version": 99, "Classes": [], "Enums": [] }"#;
+
+        match Dump::read_from_str(source) {
+            Err(DumpReadError::UnsupportedVersion(99)) => {}
+            other => panic!("Expected UnsupportedVersion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unversioned_dump() {
+        let source = r#"{
+            "Classes": [
+                {
+                    "Name": "Part",
+                    "Members": [
+                        {
+                            "MemberType": "Property",
+                            "Name": "Shape",
+                            "ValueType": { "Name": "PartType", "Category": "Enum" },
+                            "Security": "PluginSecurity",
+                            "Category": "Part"
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let dump = Dump::read_from_str(source).unwrap();
+        assert_eq!(dump.version, 1);
+        assert!(dump.enums.is_empty());
+
+        let property = dump.classes[0].properties().next().unwrap();
+        assert_eq!(property.security["Read"], "PluginSecurity");
+        assert_eq!(property.security["Write"], "PluginSecurity");
+    }
+
+    #[test]
+    fn inheritance() {
+        let source = r#"{
+            "Version": 1,
+            "Classes": [
+                {
+                    "Name": "Instance",
+                    "Superclass": "<<<ROOT>>>",
+                    "Members": [
+                        { "MemberType": "Event", "Name": "Changed", "Parameters": [], "Security": "None" },
+                        { "MemberType": "Event", "Name": "Destroying", "Parameters": [], "Security": "None" }
+                    ]
+                },
+                {
+                    "Name": "BasePart",
+                    "Superclass": "Instance",
+                    "Members": [
+                        { "MemberType": "Event", "Name": "Touched", "Parameters": [], "Security": "None" }
+                    ]
+                },
+                {
+                    "Name": "Part",
+                    "Superclass": "BasePart",
+                    "Members": [
+                        { "MemberType": "Event", "Name": "Changed", "Parameters": [], "Security": "None" }
+                    ]
+                },
+                { "Name": "Folder", "Superclass": "Instance", "Members": [] }
+            ],
+            "Enums": []
+        }"#;
+
+        let mut dump = Dump::read_from_str(source).unwrap();
+
+        let ancestors: Vec<_> = dump.ancestors("Part").map(|c| c.name.as_str()).collect();
+        assert_eq!(ancestors, vec!["BasePart", "Instance"]);
+
+        let subclasses: Vec<_> = dump
+            .subclasses("Instance")
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(subclasses, vec!["BasePart", "Folder"]);
+
+        let mut descendants: Vec<_> = dump
+            .descendants("Instance")
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        descendants.sort();
+        assert_eq!(descendants, vec!["BasePart", "Folder", "Part"]);
+
+        let members: Vec<_> = dump
+            .all_members("Part")
+            .map(|(class, member)| (class.name.as_str(), member.get_name()))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("Part", "Changed"),
+                ("BasePart", "Touched"),
+                ("Instance", "Destroying"),
+            ]
+        );
+
+        // Lookups follow the classes once they're reindexed.
+        let part = dump.classes.remove(2);
+        dump.reindex();
+        assert!(dump.find_class("Part").is_none());
+        dump.classes.insert(0, part);
+        dump.reindex();
+        assert_eq!(dump.find_class("Part").unwrap().name, "Part");
+        assert_eq!(dump.find_class("Folder").unwrap().name, "Folder");
+
+        dump.resolve_inheritance();
+        let part = dump.find_class("Part").unwrap();
+        assert_eq!(part.inherited_members.as_ref().unwrap().len(), 2);
+        assert!(part.subclasses.as_ref().unwrap().is_empty());
+    }
+
+    #[test]
+    fn preserve_fields() {
+        let dump: Dump = serde_json::from_str(EXAMPLE).unwrap();
+        let class = &dump.classes[0];
+
+        assert_eq!(class.memory_category.as_deref(), Some("PhysicsParts"));
+
+        let property = class.properties().next().unwrap();
+        assert_eq!(property.thread_safety.as_deref(), Some("ReadSafe"));
+        assert!(property.serialization.as_ref().unwrap().can_save);
+        assert!(property.extra.contains_key("Capabilities"));
+        assert!(!property.extra.contains_key("MemberType"));
+
+        let function = class.functions().next().unwrap();
+        assert_eq!(function.parameters[0].default.as_deref(), Some("Top"));
+
+        let deprecated = &class.members[2];
+        assert!(deprecated.tags().contains("Deprecated"));
+        assert_eq!(deprecated.preferred_replacement(), Some("Resize"));
+        assert_eq!(deprecated.tags().get("ThreadSafety").unwrap(), "Unsafe");
+
+        let tags: Vec<_> = deprecated.tags().iter().map(ToString::to_string).collect();
+        assert!(tags.contains(&"PreferredDescriptorName: Resize".to_owned()));
+
+        let tag: DumpTag = serde_json::from_str(r#"{ "Level": 3 }"#).unwrap();
+        assert_eq!(tag.to_string(), "Level: 3");
+
+        let item = &dump.enums[0].items[0];
+        assert!(item.tags.contains("Deprecated"));
+        assert!(item.extra.contains_key("LegacyNames"));
+
+        let output = serde_json::to_value(&dump).unwrap();
+        let member = &output["Classes"][0]["Members"][0];
+        assert_eq!(member["MemberType"], "Property");
+        assert_eq!(member["Capabilities"][0], "Basic");
+        assert_eq!(member["Serialization"]["CanLoad"], true);
+        assert_eq!(output["Enums"][0]["Items"][0]["LegacyNames"][0], "Sphere");
+        assert_eq!(
+            output["Classes"][0]["Members"][2]["Tags"][1]["PreferredDescriptorName"],
+            "Resize"
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+//! Descriptions from the Roblox Developer Hub, either requested from the site
+//! or read from class pages saved ahead of time.
+
+use std::{collections::HashMap, fmt, fs, io, path::Path};
 
 use crate::{devhub, dump::Dump};
 
@@ -6,18 +9,92 @@ pub struct DevHubData {
     pub classes: HashMap<String, DevHubClass>,
 }
 
-#[allow(dead_code)]
 impl DevHubData {
-    pub fn fetch(dump: &Dump) -> DevHubData {
+    /// Requests the DevHub page of every class in the dump. Classes without a
+    /// page are skipped.
+    pub fn fetch(dump: &Dump) -> Result<DevHubData, DevHubReadError> {
         let mut classes = HashMap::new();
 
         for class in &dump.classes {
-            if let Some(page) = request_instance_page(&class.name) {
-                classes.insert(class.name.clone(), instance_page_to_class(&page));
+            let url = instance_page_url(&class.name);
+
+            if let Some(page) = request_instance_page(&url)? {
+                classes.insert(class.name.clone(), instance_page_to_class(&page, &url)?);
+            }
+        }
+
+        Ok(DevHubData { classes })
+    }
+
+    /// Reads class pages that were saved from the DevHub ahead of time, one
+    /// JSON file per class.
+    pub fn read_from_dir(path: &Path) -> Result<DevHubData, DevHubReadError> {
+        let mut classes = HashMap::new();
+
+        for entry in fs::read_dir(path)? {
+            let entry_path = entry?.path();
+
+            if entry_path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                != Some("json")
+            {
+                continue;
+            }
+
+            let contents = fs::read_to_string(&entry_path)?;
+            let page: devhub::InstancePage = serde_json::from_str(&contents)?;
+            let class = instance_page_to_class(&page, &entry_path.display().to_string())?;
+
+            classes.insert(class.name.clone(), class);
+        }
+
+        Ok(DevHubData { classes })
+    }
+}
+
+#[derive(Debug)]
+pub enum DevHubReadError {
+    Io(io::Error),
+    InvalidJson(serde_json::Error),
+    Request(reqwest::Error),
+
+    /// A page, named by its URL or path, doesn't contain a class section.
+    MissingClass(String),
+}
+
+impl fmt::Display for DevHubReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DevHubReadError::Io(error) => write!(formatter, "{}", error),
+            DevHubReadError::InvalidJson(error) => write!(formatter, "{}", error),
+            DevHubReadError::Request(error) => write!(formatter, "{}", error),
+            DevHubReadError::MissingClass(page) => {
+                write!(
+                    formatter,
+                    "the DevHub page {} doesn't describe a class",
+                    page
+                )
             }
         }
+    }
+}
+
+impl From<io::Error> for DevHubReadError {
+    fn from(error: io::Error) -> DevHubReadError {
+        DevHubReadError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for DevHubReadError {
+    fn from(error: serde_json::Error) -> DevHubReadError {
+        DevHubReadError::InvalidJson(error)
+    }
+}
 
-        DevHubData { classes }
+impl From<reqwest::Error> for DevHubReadError {
+    fn from(error: reqwest::Error) -> DevHubReadError {
+        DevHubReadError::Request(error)
     }
 }
 
@@ -32,27 +109,36 @@ pub struct DevHubProperty {
     pub description: String,
 }
 
-#[allow(dead_code)]
-fn request_instance_page(name: &str) -> Option<devhub::InstancePage> {
-    let url = format!(
+fn instance_page_url(name: &str) -> String {
+    format!(
         "https://developer.roblox.com/api-reference/class/{}.json",
         name
-    );
-
-    println!("Requesting {}", url);
+    )
+}
 
-    let mut response = reqwest::get(&url).ok()?;
+/// Requests a class's page, returning `None` if the DevHub doesn't have one.
+fn request_instance_page(url: &str) -> Result<Option<devhub::InstancePage>, DevHubReadError> {
+    let mut response = reqwest::get(url)?;
 
     if !response.status().is_success() {
-        println!("Request failed.");
-        return None;
+        return Ok(None);
     }
 
-    response.json().expect("Couldn't parse JSON response")
+    Ok(Some(response.json()?))
 }
 
-fn instance_page_to_class(page: &devhub::InstancePage) -> DevHubClass {
-    let class = &page.entry.modular_blocks[0].api_class_section.current_class[0];
+/// Converts a page into a class, using `page_name` to say which page is at
+/// fault if it doesn't contain one.
+fn instance_page_to_class(
+    page: &devhub::InstancePage,
+    page_name: &str,
+) -> Result<DevHubClass, DevHubReadError> {
+    let class = page
+        .entry
+        .modular_blocks
+        .first()
+        .and_then(|block| block.api_class_section.current_class.first())
+        .ok_or_else(|| DevHubReadError::MissingClass(page_name.to_owned()))?;
 
     let mut properties = Vec::new();
 
@@ -67,7 +153,7 @@ fn instance_page_to_class(page: &devhub::InstancePage) -> DevHubClass {
         });
     }
 
-    DevHubClass {
+    Ok(DevHubClass {
         name: class.title.clone(),
         description: class
             .description
@@ -75,7 +161,7 @@ fn instance_page_to_class(page: &devhub::InstancePage) -> DevHubClass {
             .cloned()
             .unwrap_or_else(String::new),
         properties,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -85,6 +171,28 @@ mod test {
     #[test]
     fn get_part_page() {
         // FIXME: This seems like a pretty poor unit test, hitting the network!
-        let _part = request_instance_page("Part");
+        let _part = request_instance_page(&instance_page_url("Part"));
+    }
+
+    #[test]
+    fn pages_without_a_class() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("Part.json");
+        fs::write(
+            &path,
+            r#"{ "entry": { "modular_blocks": [{ "api_class_section": { "current_class": [] } }] } }"#,
+        )
+        .unwrap();
+
+        match DevHubData::read_from_dir(directory.path()) {
+            Err(DevHubReadError::MissingClass(page)) => {
+                assert_eq!(page, path.display().to_string())
+            }
+            Err(error) => panic!("expected a missing class error, got {}", error),
+            Ok(_) => panic!("expected a missing class error"),
+        }
+
+        fs::write(&path, r#"{ "entry": { "modular_blocks": [] } }"#).unwrap();
+        assert!(DevHubData::read_from_dir(directory.path()).is_err());
     }
 }
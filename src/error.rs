@@ -0,0 +1,291 @@
+//! The error type shared by everything that loads sources and writes outputs,
+//! which records what Dumpling was doing when it failed.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    dump::DumpReadError, dump_devhub::DevHubReadError, history::HistoryReadError,
+    precedence::PrecedenceReadError, project::ProjectReadError,
+    reflection_metadata::ReflectionMetadataReadError, supplement::ReadError,
+};
+
+#[derive(Debug)]
+pub struct Error {
+    pub stage: Stage,
+
+    /// The file being read or written, if there was one.
+    pub path: Option<PathBuf>,
+
+    /// The output being built, like `miniwiki`, if there was one.
+    pub target: Option<String>,
+
+    /// Boxed since some of the wrapped errors are large, and `Error` is
+    /// returned from nearly every fallible function.
+    pub kind: Box<ErrorKind>,
+}
+
+impl Error {
+    pub fn new<K: Into<ErrorKind>>(stage: Stage, kind: K) -> Error {
+        Error {
+            stage,
+            path: None,
+            target: None,
+            kind: Box::new(kind.into()),
+        }
+    }
+
+    pub fn with_path(mut self, path: &Path) -> Error {
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    pub fn with_target(mut self, target: &str) -> Error {
+        self.target = Some(target.to_owned());
+        self
+    }
+
+    /// The process exit code that reports this error, so that scripts can
+    /// tell failures apart. Missing inputs are configuration mistakes no matter
+    /// which stage needed them.
+    pub fn exit_code(&self) -> i32 {
+        match *self.kind {
+            ErrorKind::MissingInput { .. } => Stage::LoadProject.exit_code(),
+            _ => self.stage.exit_code(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(target) = &self.target {
+            write!(formatter, "{}: ", target)?;
+        }
+
+        write!(formatter, "error while {}", self.stage)?;
+
+        if let Some(path) = &self.path {
+            write!(formatter, " ({})", path.display())?;
+        }
+
+        write!(formatter, ": {}", self.kind)
+    }
+}
+
+/// What Dumpling was doing when an error happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    LoadProject,
+    LoadPrecedence,
+    LoadDump,
+    LoadReflectionMetadata,
+    LoadContent,
+    LoadHistory,
+    LoadDevHub,
+    Generate,
+    WriteOutput,
+}
+
+impl Stage {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Stage::LoadProject | Stage::LoadPrecedence => 2,
+            Stage::LoadDump => 3,
+            Stage::LoadReflectionMetadata => 4,
+            Stage::LoadContent => 5,
+            Stage::LoadHistory | Stage::LoadDevHub => 6,
+            Stage::Generate | Stage::WriteOutput => 7,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            Stage::LoadProject => "loading the project file",
+            Stage::LoadPrecedence => "loading the precedence config",
+            Stage::LoadDump => "loading the JSON API dump",
+            Stage::LoadReflectionMetadata => "loading ReflectionMetadata",
+            Stage::LoadContent => "loading content",
+            Stage::LoadHistory => "loading the API dump history",
+            Stage::LoadDevHub => "loading DevHub pages",
+            Stage::Generate => "generating output",
+            Stage::WriteOutput => "writing output",
+        };
+
+        write!(formatter, "{}", description)
+    }
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// An input that has no default wasn't given, by either the command line
+    /// or the project file.
+    MissingInput {
+        flag: &'static str,
+        key: &'static str,
+    },
+
+    /// A class named on the command line isn't in the dump.
+    UnknownClass(String),
+
+    /// A command that writes into the content directory was given a single
+    /// content file instead.
+    ContentNotDirectory,
+    Dump(DumpReadError),
+    ReflectionMetadata(ReflectionMetadataReadError),
+    Content(ReadError),
+    Precedence(PrecedenceReadError),
+    Project(ProjectReadError),
+    DevHub(DevHubReadError),
+    History(HistoryReadError),
+    Json(serde_json::Error),
+    Format(fmt::Error),
+    Io(io::Error),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingInput { flag, key } => write!(
+                formatter,
+                "no {} was given. Pass --{} or set {} in {}.",
+                flag,
+                flag,
+                key,
+                crate::project::PROJECT_FILE_NAME
+            ),
+            ErrorKind::UnknownClass(name) => {
+                write!(formatter, "there is no class named {} in the dump", name)
+            }
+            ErrorKind::ContentNotDirectory => write!(
+                formatter,
+                "the content path is a single file, but this command needs a directory to write into"
+            ),
+            ErrorKind::Dump(error) => write!(formatter, "{}", error),
+            ErrorKind::ReflectionMetadata(error) => write!(formatter, "{}", error),
+            ErrorKind::Content(error) => write!(formatter, "{}", error),
+            ErrorKind::Precedence(error) => write!(formatter, "{}", error),
+            ErrorKind::Project(error) => write!(formatter, "{}", error),
+            ErrorKind::DevHub(error) => write!(formatter, "{}", error),
+            ErrorKind::History(error) => write!(formatter, "{}", error),
+            ErrorKind::Json(error) => write!(formatter, "{}", error),
+            ErrorKind::Format(error) => write!(formatter, "{}", error),
+            ErrorKind::Io(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl From<DumpReadError> for ErrorKind {
+    fn from(error: DumpReadError) -> ErrorKind {
+        ErrorKind::Dump(error)
+    }
+}
+
+impl From<ReflectionMetadataReadError> for ErrorKind {
+    fn from(error: ReflectionMetadataReadError) -> ErrorKind {
+        ErrorKind::ReflectionMetadata(error)
+    }
+}
+
+impl From<ReadError> for ErrorKind {
+    fn from(error: ReadError) -> ErrorKind {
+        ErrorKind::Content(error)
+    }
+}
+
+impl From<PrecedenceReadError> for ErrorKind {
+    fn from(error: PrecedenceReadError) -> ErrorKind {
+        ErrorKind::Precedence(error)
+    }
+}
+
+impl From<ProjectReadError> for ErrorKind {
+    fn from(error: ProjectReadError) -> ErrorKind {
+        ErrorKind::Project(error)
+    }
+}
+
+impl From<DevHubReadError> for ErrorKind {
+    fn from(error: DevHubReadError) -> ErrorKind {
+        ErrorKind::DevHub(error)
+    }
+}
+
+impl From<HistoryReadError> for ErrorKind {
+    fn from(error: HistoryReadError) -> ErrorKind {
+        ErrorKind::History(error)
+    }
+}
+
+impl From<serde_json::Error> for ErrorKind {
+    fn from(error: serde_json::Error) -> ErrorKind {
+        ErrorKind::Json(error)
+    }
+}
+
+impl From<fmt::Error> for ErrorKind {
+    fn from(error: fmt::Error) -> ErrorKind {
+        ErrorKind::Format(error)
+    }
+}
+
+impl From<io::Error> for ErrorKind {
+    fn from(error: io::Error) -> ErrorKind {
+        ErrorKind::Io(error)
+    }
+}
+
+/// Describes why Roblox Studio couldn't be found, since `roblox_install`
+/// doesn't.
+pub(crate) fn fmt_roblox_install_error(
+    error: &roblox_install::Error,
+    formatter: &mut fmt::Formatter,
+) -> fmt::Result {
+    match error {
+        roblox_install::Error::MalformedRegistry => write!(
+            formatter,
+            "could not find Roblox Studio because its registry entries are malformed"
+        ),
+        roblox_install::Error::PlatformNotSupported => write!(
+            formatter,
+            "could not find Roblox Studio because it isn't supported on this platform"
+        ),
+        roblox_install::Error::RegistryError(error) => {
+            write!(formatter, "could not find Roblox Studio: {}", error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn context_and_exit_codes() {
+        let error = Error::new(
+            Stage::LoadContent,
+            io::Error::new(io::ErrorKind::NotFound, "not found"),
+        )
+        .with_path(Path::new("content"))
+        .with_target("miniwiki");
+
+        assert_eq!(
+            error.to_string(),
+            "miniwiki: error while loading content (content): not found"
+        );
+        assert_eq!(error.exit_code(), 5);
+
+        let error = Error::new(
+            Stage::LoadContent,
+            ErrorKind::MissingInput {
+                flag: "output",
+                key: "Outputs.Miniwiki.Output",
+            },
+        );
+        assert_eq!(error.exit_code(), 2);
+        assert!(error.to_string().contains("Pass --output"));
+    }
+}
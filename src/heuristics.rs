@@ -1,7 +1,10 @@
 //! Contains heuristics to guess at and clean up content sourced from outside
 //! Dumping itself.
 
-use crate::dump::{ContentSource, Dump};
+use crate::{
+    dump::{ContentSource, Dump},
+    precedence::{self, Precedence},
+};
 
 /// A handy function to capitalize a string, based on a good solution from:
 /// https://stackoverflow.com/a/38406885/802794
@@ -13,13 +16,29 @@ fn make_first_letter_uppercase(input: &str) -> String {
     }
 }
 
-/// Members with camelCase names are probably deprecated.
-pub fn camelcase_members_probably_deprecated(dump: &mut Dump) {
+/// Marks members that have been superseded by another member as deprecated:
+/// members the dump names a `PreferredDescriptorName` replacement for, and
+/// camelCase members with a PascalCase counterpart.
+pub fn superseded_members_probably_deprecated(dump: &mut Dump, precedence: &Precedence) {
+    let source = ContentSource::Heuristic;
+    let add_tags = precedence.tags.contains(&source);
+
     for class in dump.classes.iter_mut() {
         let mut fixups: Vec<(usize, String)> = Vec::new();
 
         for (index, member) in class.members.iter().enumerate() {
-            let first_char = member.get_name().chars().nth(0).unwrap();
+            // Newer dumps tell us exactly what to use instead, whatever the
+            // member's casing.
+            if let Some(preferred_name) = member.preferred_replacement() {
+                fixups.push((index, preferred_name.to_owned()));
+                continue;
+            }
+
+            // Unknown members may not have a name at all.
+            let first_char = match member.get_name().chars().next() {
+                Some(first_char) => first_char,
+                None => continue,
+            };
 
             if first_char.is_lowercase() {
                 let fixed_name = make_first_letter_uppercase(member.get_name());
@@ -44,8 +63,65 @@ pub fn camelcase_members_probably_deprecated(dump: &mut Dump) {
                 member.get_name(),
                 fixed_name
             );
-            member.set_description(description, ContentSource::Heuristic);
-            member.add_tag("Deprecated");
+
+            precedence::offer_member_description(
+                &precedence.member_descriptions,
+                member,
+                &description,
+                source,
+            );
+
+            if add_tags {
+                member.add_tag("Deprecated", source);
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn preferred_descriptor_name() {
+        let mut dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Instance",
+                    "Members": [
+                        {
+                            "MemberType": "Function",
+                            "Name": "Destroy",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "null", "Category": "Primitive" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "Remove",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "null", "Category": "Primitive" },
+                            "Security": "None",
+                            "Tags": [{ "PreferredDescriptorName": "Destroy" }]
+                        }
+                    ]
+                }],
+                "Enums": []
+            }"#,
+        )
+        .unwrap();
+
+        superseded_members_probably_deprecated(&mut dump, &Precedence::default());
+
+        let class = &dump.classes[0];
+        assert!(!class.members[0].tags().contains("Deprecated"));
+
+        let remove = &class.members[1];
+        assert!(remove.tags().contains("Deprecated"));
+        assert_eq!(
+            remove.description(),
+            Some("`Remove` is deprecated. Use `Destroy` instead.")
+        );
+    }
+}
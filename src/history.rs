@@ -0,0 +1,329 @@
+//! Builds a record of when classes, members, and enum items were added and
+//! removed by scanning a directory of historical JSON API dumps.
+//!
+//! Dumps are ordered by their file names, which should be the Roblox version
+//! each dump came from, like `0.400.0.json`. Runs of digits are compared
+//! numerically so that `0.99.0.json` sorts before `0.400.0.json`. The dump
+//! being documented should be pushed onto the history as its newest version,
+//! so that items new in it are marked as added.
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::dump::{Dump, DumpHistoryGap, DumpItemHistory, DumpReadError};
+
+#[derive(Debug)]
+pub struct VersionHistory {
+    /// The name of every dump in the history, oldest first.
+    pub versions: Vec<String>,
+
+    /// The runs of consecutive dumps each target appeared in, oldest first, as
+    /// indices into `versions`. A target that was removed and later added
+    /// back has more than one run.
+    appearances: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl VersionHistory {
+    /// Reads every JSON API dump in the given directory, ordered by file name.
+    pub fn read_from_dir(path: &Path) -> Result<VersionHistory, HistoryReadError> {
+        let mut dumps = Vec::new();
+
+        for entry in fs::read_dir(path)? {
+            let entry_path = entry?.path();
+
+            let is_json = entry_path
+                .extension()
+                .map(|extension| extension == "json")
+                .unwrap_or(false);
+
+            if !is_json {
+                continue;
+            }
+
+            let name = match entry_path.file_stem() {
+                Some(stem) => stem.to_string_lossy().into_owned(),
+                None => return Err(HistoryReadError::InvalidFileName(entry_path)),
+            };
+
+            let dump =
+                Dump::read_from_file(&entry_path).map_err(|error| HistoryReadError::Dump {
+                    path: entry_path.clone(),
+                    error,
+                })?;
+
+            dumps.push((name, dump));
+        }
+
+        dumps.sort_by(|(a_name, _), (b_name, _)| compare_version_names(a_name, b_name));
+
+        Ok(VersionHistory::from_dumps(&dumps))
+    }
+
+    /// Builds a history out of a list of named dumps, which should be ordered
+    /// from oldest to newest.
+    pub fn from_dumps(dumps: &[(String, Dump)]) -> VersionHistory {
+        let mut history = VersionHistory {
+            versions: Vec::new(),
+            appearances: HashMap::new(),
+        };
+
+        for (name, dump) in dumps {
+            history.push(name.clone(), dump);
+        }
+
+        history
+    }
+
+    /// Adds a dump that's newer than every dump already in the history.
+    pub fn push(&mut self, name: String, dump: &Dump) {
+        let index = self.versions.len();
+        self.versions.push(name);
+
+        let mut record = |target: String| {
+            let runs = self.appearances.entry(target).or_default();
+
+            match runs.last_mut() {
+                // Targets can show up more than once in the same dump.
+                Some((_, last)) if *last + 1 >= index => *last = index,
+                _ => runs.push((index, index)),
+            }
+        };
+
+        for class in &dump.classes {
+            for member in &class.members {
+                record(format!("{}.{}", class.name, member.get_name()));
+            }
+
+            record(class.name.clone());
+        }
+
+        for dump_enum in &dump.enums {
+            for item in &dump_enum.items {
+                record(format!("Enum.{}.{}", dump_enum.name, item.name));
+            }
+        }
+    }
+
+    /// The name of the newest dump in the history.
+    pub fn newest(&self) -> Option<&str> {
+        self.versions.last().map(String::as_str)
+    }
+
+    /// Looks up when a target, like `Part.Size`, first and last appeared.
+    pub fn get(&self, target: &str) -> Option<DumpItemHistory> {
+        let runs = self.appearances.get(target)?;
+        let (first, _) = *runs.first()?;
+        let (latest_first, last) = *runs.last()?;
+
+        let gaps = runs
+            .windows(2)
+            .map(|pair| DumpHistoryGap {
+                removed_in: self.versions[pair[0].1 + 1].clone(),
+                readded_in: self.versions[pair[1].0].clone(),
+            })
+            .collect();
+
+        Some(DumpItemHistory {
+            first_seen: self.versions[first].clone(),
+            last_seen: self.versions[last].clone(),
+            added_in: if latest_first > 0 {
+                Some(self.versions[latest_first].clone())
+            } else {
+                None
+            },
+            removed_in: self.versions.get(last + 1).cloned(),
+            gaps,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum HistoryReadError {
+    Io(io::Error),
+
+    /// One of the dumps in the history couldn't be read.
+    Dump {
+        path: PathBuf,
+        error: DumpReadError,
+    },
+
+    /// A dump's file name has no stem to name its version with, like `.json`.
+    InvalidFileName(PathBuf),
+}
+
+impl fmt::Display for HistoryReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryReadError::Io(error) => write!(formatter, "{}", error),
+            HistoryReadError::Dump { path, error } => {
+                write!(formatter, "{}: {}", path.display(), error)
+            }
+            HistoryReadError::InvalidFileName(path) => write!(
+                formatter,
+                "{} has no file name to use as its version",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for HistoryReadError {
+    fn from(error: io::Error) -> HistoryReadError {
+        HistoryReadError::Io(error)
+    }
+}
+
+/// Compares two dump names, treating runs of digits as numbers.
+fn compare_version_names(a: &str, b: &str) -> Ordering {
+    let a_chunks = split_digit_runs(a);
+    let b_chunks = split_digit_runs(b);
+
+    for (a_chunk, b_chunk) in a_chunks.iter().zip(b_chunks.iter()) {
+        let ordering = match (a_chunk.parse::<u64>(), b_chunk.parse::<u64>()) {
+            (Ok(a_number), Ok(b_number)) => a_number.cmp(&b_number),
+            _ => a_chunk.cmp(b_chunk),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a_chunks.len().cmp(&b_chunks.len())
+}
+
+fn split_digit_runs(input: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut last_was_digit = None;
+
+    for (index, character) in input.char_indices() {
+        let is_digit = character.is_ascii_digit();
+
+        if last_was_digit.is_some() && last_was_digit != Some(is_digit) {
+            chunks.push(&input[start..index]);
+            start = index;
+        }
+
+        last_was_digit = Some(is_digit);
+    }
+
+    if start < input.len() {
+        chunks.push(&input[start..]);
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dump_with_members(members: &[&str]) -> Dump {
+        let members = members
+            .iter()
+            .map(|name| {
+                format!(
+                    r#"{{ "MemberType": "Event", "Name": "{}", "Parameters": [], "Security": "None" }}"#,
+                    name
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let source = format!(
+            r#"{{ "Version": 1, "Classes": [{{ "Name": "Part", "Members": [{}] }}], "Enums": [] }}"#,
+            members
+        );
+
+        serde_json::from_str(&source).unwrap()
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let history = VersionHistory::from_dumps(&[
+            ("1".to_owned(), dump_with_members(&["Touched"])),
+            (
+                "2".to_owned(),
+                dump_with_members(&["Touched", "TouchEnded"]),
+            ),
+            ("3".to_owned(), dump_with_members(&["TouchEnded"])),
+        ]);
+
+        let touched = history.get("Part.Touched").unwrap();
+        assert_eq!(touched.first_seen, "1");
+        assert_eq!(touched.last_seen, "2");
+        assert_eq!(touched.added_in, None);
+        assert_eq!(touched.removed_in, Some("3".to_owned()));
+
+        let touch_ended = history.get("Part.TouchEnded").unwrap();
+        assert_eq!(touch_ended.added_in, Some("2".to_owned()));
+        assert_eq!(touch_ended.removed_in, None);
+
+        assert!(history.get("Part.Destroyed").is_none());
+    }
+
+    #[test]
+    fn current_dump_and_gaps() {
+        let mut history = VersionHistory::from_dumps(&[
+            ("1".to_owned(), dump_with_members(&["Touched"])),
+            ("2".to_owned(), dump_with_members(&[])),
+            ("3".to_owned(), dump_with_members(&["Touched"])),
+        ]);
+        history.push(
+            "current".to_owned(),
+            &dump_with_members(&["Touched", "Destroying"]),
+        );
+
+        let destroying = history.get("Part.Destroying").unwrap();
+        assert_eq!(destroying.added_in, Some("current".to_owned()));
+        assert_eq!(destroying.removed_in, None);
+
+        let touched = history.get("Part.Touched").unwrap();
+        assert_eq!(touched.first_seen, "1");
+        assert_eq!(touched.last_seen, "current");
+        assert_eq!(touched.added_in, Some("3".to_owned()));
+        assert_eq!(
+            touched.gaps,
+            vec![DumpHistoryGap {
+                removed_in: "2".to_owned(),
+                readded_in: "3".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn version_name_ordering() {
+        let mut names = vec!["0.400.0", "0.99.1", "0.99.0", "version-10", "version-9"];
+        names.sort_by(|a, b| compare_version_names(a, b));
+
+        assert_eq!(
+            names,
+            vec!["0.99.0", "0.99.1", "0.400.0", "version-9", "version-10"]
+        );
+    }
+
+    #[test]
+    fn read_from_dir() {
+        let directory = tempfile::tempdir().unwrap();
+        let empty = r#"{ "Version": 1, "Classes": [], "Enums": [] }"#;
+        fs::write(directory.path().join("0.10.0.json"), empty).unwrap();
+        fs::write(directory.path().join("0.9.0.json"), empty).unwrap();
+        fs::write(directory.path().join("notes.txt"), "Not a dump.").unwrap();
+
+        let history = VersionHistory::read_from_dir(directory.path()).unwrap();
+        assert_eq!(history.versions, vec!["0.9.0", "0.10.0"]);
+
+        let bad_path = directory.path().join("0.11.0.json");
+        fs::write(&bad_path, "{").unwrap();
+
+        match VersionHistory::read_from_dir(directory.path()) {
+            Err(HistoryReadError::Dump { path, .. }) => assert_eq!(path, bad_path),
+            other => panic!("expected a dump error, got {:?}", other),
+        }
+    }
+}
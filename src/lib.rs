@@ -0,0 +1,58 @@
+//! Dumpling combines Roblox's JSON API dump with descriptions and other
+//! information from `ReflectionMetadata.xml`, community-written content, the
+//! DevHub, and older dumps, then turns the result into documentation.
+//!
+//! Using Dumpling as a library takes three steps: load each source, merge
+//! them into the dump with the functions in [`merge`], and hand the result to
+//! an emitter like [`miniwiki::emit_wiki`], or serialize it to JSON.
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use dumpling::{
+//!     dump::Dump,
+//!     heuristics, merge,
+//!     miniwiki::{self, WikiOptions},
+//!     precedence::Precedence,
+//!     reflection_metadata::ReflectionMetadata,
+//!     supplement::SupplementalData,
+//! };
+//!
+//! let precedence = Precedence::default();
+//!
+//! let mut dump = Dump::read_from_file(Path::new("api-dump.json")).unwrap();
+//! let metadata = ReflectionMetadata::read_from_file(Path::new("ReflectionMetadata.xml")).unwrap();
+//! let content = SupplementalData::read_from_path(Path::new("content")).unwrap();
+//!
+//! merge::apply_reflection_metadata(&mut dump, &metadata, &precedence);
+//! heuristics::superseded_members_probably_deprecated(&mut dump, &precedence);
+//! merge::apply_supplemental(&mut dump, &content, &precedence);
+//!
+//! let mut output = String::new();
+//! miniwiki::emit_wiki(&dump, &WikiOptions::default(), &mut output).unwrap();
+//! ```
+//!
+//! [`project::Project`] does the same loading and merging the way the command
+//! line does, driven by a `dumpling.toml`. Its loaders return
+//! [`error::Error`], which records the stage and file that failed.
+
+#![recursion_limit = "1024"]
+
+pub mod check;
+pub mod coverage;
+pub mod diff;
+pub mod disagreement;
+pub mod dump;
+pub mod dump_devhub;
+pub mod error;
+pub mod heuristics;
+pub mod history;
+pub mod merge;
+pub mod miniwiki;
+pub mod precedence;
+pub mod project;
+pub mod reflection_metadata;
+pub mod scaffold;
+pub mod supplement;
+
+mod devhub;
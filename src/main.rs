@@ -1,204 +1,300 @@
-#![recursion_limit = "1024"]
-
-mod devhub;
-mod dump;
-mod dump_devhub;
-mod heuristics;
-mod miniwiki;
-mod reflection_metadata;
-mod supplement;
-
-use std::{fs, path::Path};
-
-use clap::{App, Arg, SubCommand};
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
 
-use crate::{
-    dump::{ContentSource, Dump, DumpClassMember, DumpReturnType, DumpType},
-    dump_devhub::DevHubData,
-    reflection_metadata::ReflectionMetadata,
-    supplement::SupplementalData,
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+
+use dumpling::{
+    check::{self, CheckReport, Severity},
+    coverage::{self, CoverageFilter, CoverageReport},
+    diff::{self, DumpDiff},
+    disagreement::{self, DisagreementReport},
+    dump::Dump,
+    error::{Error, ErrorKind, Stage},
+    miniwiki::{self, WikiOptions},
+    precedence::Precedence,
+    project::{CoverageFormat, DisagreementFormat, Project},
+    scaffold,
 };
 
-fn apply_reflection_metadata(dump: &mut Dump, metadata: &ReflectionMetadata) {
-    for class in dump.classes.iter_mut() {
-        if let Some(metadata_class) = metadata.classes.get(&class.name) {
-            if !metadata_class.summary.is_empty() {
-                class.description = Some(metadata_class.summary.clone());
-            }
+/// Finds the output path of a command that must write to a file, which has
+/// to be given either on the command line or in the project file.
+fn required_output<'a>(path: &'a Option<PathBuf>, key: &'static str) -> Result<&'a Path, Error> {
+    path.as_deref().ok_or_else(|| {
+        Error::new(
+            Stage::WriteOutput,
+            ErrorKind::MissingInput {
+                flag: "output",
+                key,
+            },
+        )
+    })
+}
 
-            for member in class.members.iter_mut() {
-                if let Some(meta_member) = metadata_class.members.get(member.get_name()) {
-                    if !meta_member.summary.is_empty() {
-                        member.set_description(
-                            meta_member.summary.clone(),
-                            ContentSource::ReflectionMetadata,
-                        );
-                    }
-                }
-            }
+/// Writes a command's output to a file, or to stdout if there's no file.
+fn write_output(output_path: Option<&Path>, output: &str) -> Result<(), Error> {
+    match output_path {
+        Some(output_path) => fs::write(output_path, output)
+            .map_err(|error| Error::new(Stage::WriteOutput, error).with_path(output_path)),
+        None => {
+            print!("{}", output);
+            Ok(())
         }
     }
 }
 
-fn simple_name_to_dump_type(name: &str) -> DumpType {
-    let mut n: &str = name;
-    let c = String::from(if name.starts_with("Enum.") {
-        n = &name[5..];
-        "Enum"
-    } else {
-        // TODO: Primitive, Class, and DataType. Also generic Group types. Not sure what to do with unique tables.
-        "TODO"
-    });
-    DumpType {
-        name: String::from(n),
-        category: c,
-    }
+struct MiniwikiOptions<'a> {
+    project: &'a Project,
+    output_path: &'a Path,
+    edit_url_template: Option<&'a str>,
 }
 
-fn apply_supplemental(dump: &mut Dump, content: &SupplementalData) {
-    for class in dump.classes.iter_mut() {
-        if let Some(description) = content.item_descriptions.get(&class.name) {
-            class.description = Some(description.prose.clone());
-            class.description_source = Some(ContentSource::Supplemental);
-        }
+fn miniwiki(options: &MiniwikiOptions) -> Result<(), Error> {
+    let dump = options.project.load_merged_dump()?;
 
-        for member in class.members.iter_mut() {
-            match member {
-                DumpClassMember::Function(function) => {
-                    if let Some(description) = content
-                        .item_descriptions
-                        .get(&format!("{}.{}", &class.name, &function.name))
-                    {
-                        function.description = Some(description.prose.clone());
-                        function.description_source = Some(ContentSource::Supplemental);
-
-                        if let Some(type_names) = &description.metadata.return_types {
-                            function.return_type = DumpReturnType::Multiple(
-                                type_names
-                                    .iter()
-                                    .map(|simple_name| simple_name_to_dump_type(&simple_name))
-                                    .collect(),
-                            );
-                        }
-                    }
-                }
-                DumpClassMember::Property(property) => {
-                    if let Some(description) = content
-                        .item_descriptions
-                        .get(&format!("{}.{}", &class.name, &property.name))
-                    {
-                        property.description = Some(description.prose.clone());
-                        property.description_source = Some(ContentSource::Supplemental);
-                    }
-                }
-                DumpClassMember::Event(event) => {
-                    if let Some(description) = content
-                        .item_descriptions
-                        .get(&format!("{}.{}", &class.name, &event.name))
-                    {
-                        event.description = Some(description.prose.clone());
-                        event.description_source = Some(ContentSource::Supplemental);
-                    }
-                }
-                DumpClassMember::Callback(callback) => {
-                    if let Some(description) = content
-                        .item_descriptions
-                        .get(&format!("{}.{}", &class.name, &callback.name))
-                    {
-                        callback.description = Some(description.prose.clone());
-                        callback.description_source = Some(ContentSource::Supplemental);
-                    }
-                }
-            }
-        }
+    let mut output = String::new();
+    let wiki_options = WikiOptions {
+        edit_url_template: options.edit_url_template,
+    };
+
+    miniwiki::emit_wiki(&dump, &wiki_options, &mut output)
+        .map_err(|error| Error::new(Stage::Generate, error))?;
+
+    write_output(Some(options.output_path), &output)
+}
+
+struct MegadumpOptions<'a> {
+    project: &'a Project,
+    output_path: &'a Path,
+    resolve_inheritance: bool,
+}
+
+fn megadump(options: &MegadumpOptions) -> Result<(), Error> {
+    let mut dump = options.project.load_merged_dump()?;
+
+    if options.resolve_inheritance {
+        dump.resolve_inheritance();
     }
+
+    let output =
+        serde_json::to_string(&dump).map_err(|error| Error::new(Stage::Generate, error))?;
+
+    write_output(Some(options.output_path), &output)
 }
 
-#[allow(dead_code)]
-fn apply_devhub(dump: &mut Dump, content: &DevHubData) {
-    for devhub_class in content.classes.values() {
-        if let Some(dump_class) = dump
-            .classes
-            .iter_mut()
-            .find(|item| item.name == devhub_class.name)
-        {
-            dump_class.description = Some(devhub_class.description.clone());
-
-            for property in &devhub_class.properties {
-                if let Some(dump_member) = dump_class
-                    .properties_mut()
-                    .find(|item| item.name == property.name)
-                {
-                    dump_member.description = Some(property.description.clone());
-                    dump_member.description_source = Some(ContentSource::DevHub);
-                }
-            }
+#[derive(Debug, Clone, Copy)]
+enum DiffFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+struct DiffOptions<'a> {
+    old_path: &'a Path,
+    new_path: &'a Path,
+    output_path: Option<&'a Path>,
+    format: DiffFormat,
+}
+
+fn diff(options: &DiffOptions) -> Result<(), Error> {
+    let read = |path| {
+        Dump::read_from_file(path)
+            .map_err(|error| Error::new(Stage::LoadDump, error).with_path(path))
+    };
+
+    let old = read(options.old_path)?;
+    let new = read(options.new_path)?;
+
+    let diff = DumpDiff::new(&old, &new);
+
+    let mut output = String::new();
+    match options.format {
+        DiffFormat::Text => diff::emit_text(&diff, &mut output)
+            .map_err(|error| Error::new(Stage::Generate, error))?,
+        DiffFormat::Markdown => diff::emit_markdown(&diff, &mut output)
+            .map_err(|error| Error::new(Stage::Generate, error))?,
+        DiffFormat::Json => {
+            output =
+                serde_json::to_string(&diff).map_err(|error| Error::new(Stage::Generate, error))?
         }
     }
-}
 
-fn load_combined_dump(
-    dump_path: Option<&Path>,
-    reflection_metadata_path: Option<&Path>,
-    content_path: &Path,
-) -> Dump {
-    let mut dump = Dump::read(dump_path).expect("Could not load JSON API dump");
+    write_output(options.output_path, &output)
+}
 
-    let metadata = ReflectionMetadata::read(reflection_metadata_path)
-        .expect("Could not load ReflectionMetadata!");
+struct CheckOptions<'a> {
+    project: &'a Project,
+    deny_warnings: bool,
+}
 
-    let content =
-        SupplementalData::read_from_path(content_path).expect("Could not load content data");
+/// Checks content against the dump, returning whether it passed.
+fn check(options: &CheckOptions) -> Result<bool, Error> {
+    let project = options.project;
+    let dump = project.load_dump()?;
+    let content = project.load_content()?;
 
-    apply_reflection_metadata(&mut dump, &metadata);
-    heuristics::camelcase_members_probably_deprecated(&mut dump);
+    let report = CheckReport::new(&dump, &content);
 
-    // TODO: Disabled while we iterate on this!
-    // let devhub_data = DevHubData::fetch(&dump);
-    // apply_devhub(&mut dump, &devhub_data);
+    let mut output = String::new();
+    check::emit_text(&report, &mut output).map_err(|error| Error::new(Stage::Generate, error))?;
+    print!("{}", output);
 
-    apply_supplemental(&mut dump, &content);
+    let mut failures = report.count(Severity::Error);
+    if options.deny_warnings {
+        failures += report.count(Severity::Warning);
+    }
 
-    dump
+    Ok(failures == 0)
 }
 
-struct MiniwikiOptions<'a> {
-    output_path: &'a Path,
-    dump_path: Option<&'a Path>,
-    metadata_path: Option<&'a Path>,
-    content_path: &'a Path,
+struct CoverageOptions<'a> {
+    project: &'a Project,
+    output_path: Option<&'a Path>,
+    format: CoverageFormat,
+    filter: CoverageFilter,
 }
 
-fn miniwiki(options: &MiniwikiOptions) {
-    let dump = load_combined_dump(
-        options.dump_path,
-        options.metadata_path,
-        options.content_path,
-    );
+fn coverage(options: &CoverageOptions) -> Result<(), Error> {
+    let dump = options.project.load_merged_dump()?;
+
+    let report = CoverageReport::new(&dump, options.filter);
 
     let mut output = String::new();
-    miniwiki::emit_wiki(&dump, &mut output).expect("Could not generate Miniwiki");
+    match options.format {
+        CoverageFormat::Table => coverage::emit_table(&report, &mut output)
+            .map_err(|error| Error::new(Stage::Generate, error))?,
+        CoverageFormat::Json => {
+            output = serde_json::to_string(&report)
+                .map_err(|error| Error::new(Stage::Generate, error))?
+        }
+        CoverageFormat::Html => coverage::emit_html(&report, &mut output)
+            .map_err(|error| Error::new(Stage::Generate, error))?,
+    }
 
-    fs::write(options.output_path, &output).expect("Could not write to output file");
+    write_output(options.output_path, &output)
 }
 
-struct MegadumpOptions<'a> {
-    output_path: &'a Path,
-    dump_path: Option<&'a Path>,
-    metadata_path: Option<&'a Path>,
-    content_path: &'a Path,
+struct ScaffoldOptions<'a> {
+    project: &'a Project,
+
+    /// The classes to write stubs for, or `None` to write stubs for every
+    /// class that is missing descriptions.
+    class_names: Option<Vec<&'a str>>,
 }
 
-fn megadump(options: &MegadumpOptions) {
-    let dump = load_combined_dump(
-        options.dump_path,
-        options.metadata_path,
-        options.content_path,
-    );
+fn scaffold(options: &ScaffoldOptions) -> Result<(), Error> {
+    let project = options.project;
 
-    let output = serde_json::to_string(&dump).expect("Could not convert dump to JSON");
+    // Stubs go in one file per class, so there has to be a directory to put
+    // them in.
+    let content_path = project.content_path()?;
+    if content_path.is_file() {
+        return Err(
+            Error::new(Stage::LoadContent, ErrorKind::ContentNotDirectory).with_path(content_path),
+        );
+    }
+
+    let dump = project.load_dump()?;
+    let metadata = project.load_reflection_metadata()?;
+    let content = project.load_content()?;
+
+    if let Some(class_names) = &options.class_names {
+        for class_name in class_names {
+            if dump.find_class(class_name).is_none() {
+                return Err(Error::new(
+                    Stage::Generate,
+                    ErrorKind::UnknownClass(class_name.to_string()),
+                ));
+            }
+        }
+    }
 
-    fs::write(options.output_path, &output).expect("Could not write to output file");
+    for class in &dump.classes {
+        if let Some(class_names) = &options.class_names {
+            if !class_names.contains(&class.name.as_str()) {
+                continue;
+            }
+        }
+
+        let mut stubs = String::new();
+        let count = scaffold::emit_class_stubs(
+            class,
+            metadata
+                .as_ref()
+                .and_then(|metadata| metadata.classes.get(&class.name)),
+            &content,
+            &mut stubs,
+        )
+        .map_err(|error| Error::new(Stage::Generate, error))?;
+
+        if count == 0 {
+            continue;
+        }
+
+        // Stubs are only ever appended, so existing content is left untouched.
+        let path = content_path.join(format!("{}.md", class.name));
+        let existing = match fs::read_to_string(&path) {
+            Ok(existing) => existing,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => {
+                return Err(Error::new(Stage::LoadContent, error).with_path(&path));
+            }
+        };
+
+        let separator = if existing.is_empty() {
+            ""
+        } else if existing.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| write!(file, "{}{}", separator, stubs))
+            .map_err(|error| Error::new(Stage::WriteOutput, error).with_path(&path))?;
+
+        println!("Wrote {} stub(s) to {}", count, path.display());
+    }
+
+    Ok(())
+}
+
+struct DisagreementOptions<'a> {
+    project: &'a Project,
+    output_path: Option<&'a Path>,
+    format: DisagreementFormat,
+}
+
+fn disagreements(options: &DisagreementOptions) -> Result<(), Error> {
+    let project = options.project;
+    let dump = project.load_dump()?;
+    let metadata = project.load_reflection_metadata()?;
+    let content = project.load_content()?;
+
+    // Unlike when building docs, DevHub pages are compared whenever they're
+    // given, since the comparison doesn't use them as content.
+    let devhub = project.load_devhub()?;
+
+    let report = DisagreementReport::new(&dump, metadata.as_ref(), &content, devhub.as_ref());
+
+    let mut output = String::new();
+    match options.format {
+        DisagreementFormat::Text => disagreement::emit_text(&report, &mut output)
+            .map_err(|error| Error::new(Stage::Generate, error))?,
+        DisagreementFormat::Json => {
+            output = serde_json::to_string(&report)
+                .map_err(|error| Error::new(Stage::Generate, error))?
+        }
+    }
+
+    write_output(options.output_path, &output)
 }
 
 fn main() {
@@ -209,20 +305,61 @@ fn main() {
 
     let metadata_arg = Arg::with_name("metadata")
         .long("metadata")
-        .help("The location of the Roblox ReflectionMetadata.xml file")
+        .help("The location of the Roblox ReflectionMetadata.xml file, or - to read it from stdin")
         .takes_value(true);
 
     let content_arg = Arg::with_name("content")
         .long("content")
         .help("The location of the Roblox supplementary data")
-        .required(true)
         .takes_value(true);
 
+    let precedence_arg = Arg::with_name("precedence")
+        .long("precedence")
+        .help("A TOML file listing which content sources take precedence for each field")
+        .takes_value(true);
+
+    let history_arg = Arg::with_name("history")
+        .long("history")
+        .help("A directory of older JSON API dumps used to track when items were added and removed")
+        .takes_value(true);
+
+    let devhub_arg = Arg::with_name("devhub")
+        .long("devhub")
+        .help("A directory of DevHub class pages saved as JSON, which turns the DevHub source on")
+        .takes_value(true)
+        .conflicts_with("no-devhub");
+
+    let mut source_args = [
+        switch_args(
+            "reflection-metadata",
+            "no-reflection-metadata",
+            "Use ReflectionMetadata.xml as a content source",
+            "Don't use ReflectionMetadata.xml as a content source",
+        ),
+        switch_args(
+            "supplemental",
+            "no-supplemental",
+            "Use the content directory as a content source",
+            "Don't use the content directory as a content source",
+        ),
+        switch_args(
+            "camelcase-heuristic",
+            "no-camelcase-heuristic",
+            "Mark members superseded by a PreferredDescriptorName or a PascalCase counterpart as deprecated",
+            "Don't guess which members are deprecated",
+        ),
+    ]
+    .concat();
+    source_args.push(
+        Arg::with_name("no-devhub")
+            .long("no-devhub")
+            .help("Don't use DevHub pages as a content source"),
+    );
+
     let output_arg = Arg::with_name("output")
         .long("output")
         .short("o")
         .help("Where to output the resulting file")
-        .required(true)
         .takes_value(true);
 
     let matches = App::new("Dumpling")
@@ -235,7 +372,20 @@ fn main() {
                 .arg(dump_arg.clone())
                 .arg(metadata_arg.clone())
                 .arg(content_arg.clone())
-                .arg(output_arg.clone()),
+                .arg(precedence_arg.clone())
+                .arg(history_arg.clone())
+                .arg(devhub_arg.clone())
+                .args(&source_args)
+                .arg(output_arg.clone())
+                .arg(
+                    Arg::with_name("edit-url")
+                        .long("edit-url")
+                        .help(
+                            "A URL template for \"Edit this description\" links, which can use \
+                             {path}, {line}, and {end_line}",
+                        )
+                        .takes_value(true),
+                ),
         )
         .subcommand(
             SubCommand::with_name("megadump")
@@ -243,39 +393,385 @@ fn main() {
                 .arg(dump_arg.clone())
                 .arg(metadata_arg.clone())
                 .arg(content_arg.clone())
+                .arg(precedence_arg.clone())
+                .arg(history_arg.clone())
+                .arg(devhub_arg.clone())
+                .args(&source_args)
+                .arg(output_arg.clone())
+                .args(&switch_args(
+                    "inheritance",
+                    "no-inheritance",
+                    "Include each class's subclasses and inherited members",
+                    "Leave out each class's subclasses and inherited members",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("diff")
+                .about("Compare two JSON API dumps and report what changed")
+                .arg(
+                    Arg::with_name("old")
+                        .help("The older JSON API dump")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("new")
+                        .help("The newer JSON API dump")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .help("The format to report changes in")
+                        .possible_values(&["text", "markdown", "json"])
+                        .default_value("text")
+                        .takes_value(true),
+                )
+                .arg(output_arg.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("check")
+                .about("Check supplementary data for mistakes, like targets that don't exist")
+                .arg(dump_arg.clone())
+                .arg(content_arg.clone())
+                .args(&switch_args(
+                    "deny-warnings",
+                    "no-deny-warnings",
+                    "Fail if there are any warnings, like empty descriptions",
+                    "Only fail if there are errors",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("coverage")
+                .about("Report how much of the API has descriptions, and where they came from")
+                .arg(dump_arg.clone())
+                .arg(metadata_arg.clone())
+                .arg(content_arg.clone())
+                .arg(precedence_arg.clone())
+                .arg(devhub_arg.clone())
+                .args(&source_args)
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .help("The format to report coverage in, which is table by default")
+                        .possible_values(&["table", "json", "html"])
+                        .takes_value(true),
+                )
+                .args(&switch_args(
+                    "exclude-deprecated",
+                    "include-deprecated",
+                    "Leave out deprecated classes and members",
+                    "Count deprecated classes and members",
+                ))
+                .args(&switch_args(
+                    "exclude-hidden",
+                    "include-hidden",
+                    "Leave out hidden and non-browsable classes and members",
+                    "Count hidden and non-browsable classes and members",
+                ))
+                .arg(output_arg.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("scaffold")
+                .about("Write stub content for classes and members that have no descriptions")
+                .arg(dump_arg.clone())
+                .arg(metadata_arg.clone())
+                .arg(content_arg.clone())
+                .arg(
+                    Arg::with_name("class")
+                        .long("class")
+                        .help("A class to write stubs for")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::with_name("all-missing")
+                        .long("all-missing")
+                        .help("Write stubs for every class that is missing descriptions"),
+                )
+                .group(
+                    ArgGroup::with_name("classes")
+                        .args(&["class", "all-missing"])
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("disagreements")
+                .about("Report items where content sources disagree with the dump")
+                .arg(dump_arg.clone())
+                .arg(metadata_arg.clone())
+                .arg(content_arg.clone())
+                .arg(devhub_arg.clone())
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .help("The format to report disagreements in, which is text by default")
+                        .possible_values(&["text", "json"])
+                        .takes_value(true),
+                )
                 .arg(output_arg.clone()),
         )
         .get_matches();
 
+    if let Err(error) = run(&matches) {
+        let error = match matches.subcommand_name() {
+            Some(name) => error.with_target(name),
+            None => error,
+        };
+
+        eprintln!("{}", error);
+        process::exit(error.exit_code());
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let mut project = match env::current_dir() {
+        Ok(current_dir) => Project::discover(&current_dir)
+            .map_err(|error| Error::new(Stage::LoadProject, error))?,
+        Err(_) => None,
+    }
+    .unwrap_or_default();
+
     match matches.subcommand() {
         ("miniwiki", command_matches) => {
             let command_matches = command_matches.unwrap();
-            let output_path = Path::new(command_matches.value_of("output").unwrap());
-            let dump_path = command_matches.value_of("dump").map(Path::new);
-            let metadata_path = command_matches.value_of("metadata").map(Path::new);
-            let content_path = Path::new(command_matches.value_of("content").unwrap());
+            apply_input_arguments(&mut project, command_matches)?;
+
+            let outputs = &project.outputs.miniwiki;
+            let output_path = command_matches
+                .value_of("output")
+                .map(PathBuf::from)
+                .or_else(|| outputs.output.clone());
+            let output_path = required_output(&output_path, "Outputs.Miniwiki.Output")?;
+            let edit_url_template = command_matches
+                .value_of("edit-url")
+                .or(outputs.edit_url.as_deref());
 
             miniwiki(&MiniwikiOptions {
+                project: &project,
                 output_path,
-                dump_path,
-                metadata_path,
-                content_path,
-            });
+                edit_url_template,
+            })?;
         }
         ("megadump", command_matches) => {
             let command_matches = command_matches.unwrap();
-            let output_path = Path::new(command_matches.value_of("output").unwrap());
-            let dump_path = command_matches.value_of("dump").map(Path::new);
-            let metadata_path = command_matches.value_of("metadata").map(Path::new);
-            let content_path = Path::new(command_matches.value_of("content").unwrap());
+            apply_input_arguments(&mut project, command_matches)?;
+
+            let outputs = &project.outputs.megadump;
+            let output_path = command_matches
+                .value_of("output")
+                .map(PathBuf::from)
+                .or_else(|| outputs.output.clone());
+            let output_path = required_output(&output_path, "Outputs.Megadump.Output")?;
+            let resolve_inheritance = switch(
+                command_matches,
+                "inheritance",
+                "no-inheritance",
+                outputs.inheritance,
+            );
 
             megadump(&MegadumpOptions {
+                project: &project,
+                output_path,
+                resolve_inheritance,
+            })?;
+        }
+        ("diff", command_matches) => {
+            let command_matches = command_matches.unwrap();
+            let old_path = Path::new(command_matches.value_of("old").unwrap());
+            let new_path = Path::new(command_matches.value_of("new").unwrap());
+            let output_path = command_matches.value_of("output").map(Path::new);
+            let format = match command_matches.value_of("format").unwrap() {
+                "markdown" => DiffFormat::Markdown,
+                "json" => DiffFormat::Json,
+                _ => DiffFormat::Text,
+            };
+
+            diff(&DiffOptions {
+                old_path,
+                new_path,
                 output_path,
-                dump_path,
-                metadata_path,
-                content_path,
-            });
+                format,
+            })?;
+        }
+        ("check", command_matches) => {
+            let command_matches = command_matches.unwrap();
+            apply_input_arguments(&mut project, command_matches)?;
+
+            let deny_warnings = switch(
+                command_matches,
+                "deny-warnings",
+                "no-deny-warnings",
+                project.outputs.check.deny_warnings,
+            );
+
+            let passed = check(&CheckOptions {
+                project: &project,
+                deny_warnings,
+            })?;
+
+            if !passed {
+                process::exit(1);
+            }
+        }
+        ("coverage", command_matches) => {
+            let command_matches = command_matches.unwrap();
+            apply_input_arguments(&mut project, command_matches)?;
+
+            let outputs = &project.outputs.coverage;
+            let output_path = command_matches
+                .value_of("output")
+                .map(PathBuf::from)
+                .or_else(|| outputs.output.clone());
+            let format = match command_matches.value_of("format") {
+                Some("json") => CoverageFormat::Json,
+                Some("html") => CoverageFormat::Html,
+                Some(_) => CoverageFormat::Table,
+                None => outputs.format,
+            };
+            let filter = CoverageFilter {
+                exclude_deprecated: switch(
+                    command_matches,
+                    "exclude-deprecated",
+                    "include-deprecated",
+                    outputs.exclude_deprecated,
+                ),
+                exclude_hidden: switch(
+                    command_matches,
+                    "exclude-hidden",
+                    "include-hidden",
+                    outputs.exclude_hidden,
+                ),
+            };
+
+            coverage(&CoverageOptions {
+                project: &project,
+                output_path: output_path.as_deref(),
+                format,
+                filter,
+            })?;
+        }
+        ("scaffold", command_matches) => {
+            let command_matches = command_matches.unwrap();
+            apply_input_arguments(&mut project, command_matches)?;
+
+            let class_names = command_matches
+                .values_of("class")
+                .map(|values| values.collect());
+
+            scaffold(&ScaffoldOptions {
+                project: &project,
+                class_names,
+            })?;
+        }
+        ("disagreements", command_matches) => {
+            let command_matches = command_matches.unwrap();
+            apply_input_arguments(&mut project, command_matches)?;
+
+            let outputs = &project.outputs.disagreements;
+            let output_path = command_matches
+                .value_of("output")
+                .map(PathBuf::from)
+                .or_else(|| outputs.output.clone());
+            let format = match command_matches.value_of("format") {
+                Some("json") => DisagreementFormat::Json,
+                Some(_) => DisagreementFormat::Text,
+                None => outputs.format,
+            };
+
+            disagreements(&DisagreementOptions {
+                project: &project,
+                output_path: output_path.as_deref(),
+                format,
+            })?;
         }
         _ => eprintln!("{}", matches.usage()),
     }
+
+    Ok(())
+}
+
+/// Creates a switch and its `--no-` counterpart, which override a setting
+/// from the project file in either direction.
+fn switch_args(
+    name: &'static str,
+    negated_name: &'static str,
+    help: &'static str,
+    negated_help: &'static str,
+) -> [Arg<'static, 'static>; 2] {
+    [
+        Arg::with_name(name)
+            .long(name)
+            .help(help)
+            .conflicts_with(negated_name),
+        Arg::with_name(negated_name)
+            .long(negated_name)
+            .help(negated_help),
+    ]
+}
+
+/// Reads a switch created by `switch_args`, falling back to the project's
+/// setting if neither form was given.
+fn switch(matches: &ArgMatches, name: &str, negated_name: &str, default: bool) -> bool {
+    if matches.is_present(name) {
+        true
+    } else if matches.is_present(negated_name) {
+        false
+    } else {
+        default
+    }
+}
+
+/// Overrides the project's inputs, sources, heuristics, and precedence with
+/// any that were given on the command line. Passing `--devhub` turns the DevHub
+/// source on.
+fn apply_input_arguments(project: &mut Project, matches: &ArgMatches) -> Result<(), Error> {
+    let inputs = &mut project.inputs;
+    let arguments = [
+        ("dump", &mut inputs.dump),
+        ("metadata", &mut inputs.metadata),
+        ("content", &mut inputs.content),
+        ("history", &mut inputs.history),
+        ("devhub", &mut inputs.dev_hub),
+    ];
+
+    for (name, input) in arguments {
+        if let Some(value) = matches.value_of(name) {
+            *input = Some(PathBuf::from(value));
+        }
+    }
+
+    let sources = &mut project.sources;
+    sources.reflection_metadata = switch(
+        matches,
+        "reflection-metadata",
+        "no-reflection-metadata",
+        sources.reflection_metadata,
+    );
+    sources.dev_hub = switch(matches, "devhub", "no-devhub", sources.dev_hub);
+    sources.supplemental = switch(
+        matches,
+        "supplemental",
+        "no-supplemental",
+        sources.supplemental,
+    );
+
+    let heuristics = &mut project.heuristics;
+    heuristics.camelcase_deprecated = switch(
+        matches,
+        "camelcase-heuristic",
+        "no-camelcase-heuristic",
+        heuristics.camelcase_deprecated,
+    );
+
+    if let Some(precedence_path) = matches.value_of("precedence") {
+        let precedence_path = Path::new(precedence_path);
+
+        project.precedence = Precedence::read_from_file(precedence_path)
+            .map_err(|error| Error::new(Stage::LoadPrecedence, error).with_path(precedence_path))?;
+    }
+
+    Ok(())
 }
@@ -0,0 +1,878 @@
+//! Merges content sources into an API dump.
+//!
+//! Each `apply_` function takes one source and offers its data to the dump,
+//! and the given `Precedence` decides whether it replaces what's already
+//! there. Sources can be applied in any order, except that supplemental content
+//! with `Mode = "append"` or `"prepend"` only combines with
+//! descriptions applied before it, so it should be applied after the other
+//! sources.
+
+use std::collections::HashMap;
+
+use crate::{
+    dump::{
+        ContentSource, DescriptionCandidates, DescriptionOrigin, Dump, DumpClassMember,
+        DumpFunctionParameter, DumpReturnType, DumpType,
+    },
+    dump_devhub::DevHubData,
+    history::VersionHistory,
+    precedence::{self, Precedence},
+    reflection_metadata::ReflectionMetadata,
+    supplement::{DescriptionMode, ItemDescription, Metadata, SupplementalData},
+};
+
+/// Offers the summaries from `ReflectionMetadata.xml` as descriptions of
+/// classes, members, enums, and enum items, and attaches the other attributes
+/// it gives each of them.
+///
+/// If the precedence lets ReflectionMetadata add tags, items it marks
+/// `deprecated` are tagged `Deprecated`, and items it marks not `Browsable`
+/// are tagged `NotBrowsable`.
+pub fn apply_reflection_metadata(
+    dump: &mut Dump,
+    metadata: &ReflectionMetadata,
+    precedence: &Precedence,
+) {
+    let source = ContentSource::ReflectionMetadata;
+    let add_tags = precedence.tags.contains(&source);
+
+    for class in dump.classes.iter_mut() {
+        if let Some(metadata_class) = metadata.classes.get(&class.name) {
+            class.reflection_metadata = Some(metadata_class.attributes.clone());
+
+            if add_tags {
+                for tag in metadata_tags(
+                    metadata_class.attributes.deprecated,
+                    metadata_class.attributes.browsable,
+                ) {
+                    class.add_tag(tag, source);
+                }
+            }
+
+            if !metadata_class.summary.is_empty() {
+                precedence::offer_description(
+                    &precedence.class_descriptions,
+                    &mut class.description,
+                    &mut class.description_source,
+                    &mut class.description_candidates,
+                    &metadata_class.summary,
+                    source,
+                );
+            }
+
+            for member in class.members.iter_mut() {
+                if let Some(meta_member) = metadata_class.members.get(member.get_name()) {
+                    member.set_reflection_metadata(meta_member.attributes.clone());
+
+                    if add_tags {
+                        let attributes = &meta_member.attributes;
+
+                        for tag in metadata_tags(attributes.deprecated, attributes.browsable) {
+                            member.add_tag(tag, source);
+                        }
+                    }
+
+                    if !meta_member.summary.is_empty() {
+                        precedence::offer_member_description(
+                            &precedence.member_descriptions,
+                            member,
+                            &meta_member.summary,
+                            source,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    for dump_enum in dump.enums.iter_mut() {
+        if let Some(metadata_enum) = metadata.enums.get(&dump_enum.name) {
+            dump_enum.reflection_metadata = Some(metadata_enum.attributes.clone());
+
+            if add_tags {
+                for tag in metadata_tags(
+                    metadata_enum.attributes.deprecated,
+                    metadata_enum.attributes.browsable,
+                ) {
+                    dump_enum.add_tag(tag, source);
+                }
+            }
+
+            if !metadata_enum.summary.is_empty() {
+                precedence::offer_description(
+                    &precedence.class_descriptions,
+                    &mut dump_enum.description,
+                    &mut dump_enum.description_source,
+                    &mut dump_enum.description_candidates,
+                    &metadata_enum.summary,
+                    source,
+                );
+            }
+
+            for item in dump_enum.items.iter_mut() {
+                if let Some(metadata_item) = metadata_enum.items.get(&item.name) {
+                    item.reflection_metadata = Some(metadata_item.attributes.clone());
+
+                    if add_tags {
+                        let attributes = &metadata_item.attributes;
+
+                        for tag in metadata_tags(attributes.deprecated, attributes.browsable) {
+                            item.add_tag(tag, source);
+                        }
+                    }
+
+                    if !metadata_item.summary.is_empty() {
+                        precedence::offer_description(
+                            &precedence.member_descriptions,
+                            &mut item.description,
+                            &mut item.description_source,
+                            &mut item.description_candidates,
+                            &metadata_item.summary,
+                            source,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The tags implied by ReflectionMetadata's `deprecated` and `Browsable`
+/// attributes.
+fn metadata_tags(deprecated: Option<bool>, browsable: Option<bool>) -> Vec<&'static str> {
+    let mut tags = Vec::new();
+
+    if deprecated == Some(true) {
+        tags.push("Deprecated");
+    }
+
+    if browsable == Some(false) {
+        tags.push("NotBrowsable");
+    }
+
+    tags
+}
+
+/// Applies the prose of a supplemental item to a description if the order
+/// prefers supplemental content over the current description, combining them
+/// according to its `Mode`.
+fn offer_supplemental_description(
+    order: &[ContentSource],
+    description: &mut Option<String>,
+    description_source: &mut Option<ContentSource>,
+    description_origin: &mut Option<DescriptionOrigin>,
+    candidates: &mut DescriptionCandidates,
+    content: &SupplementalData,
+    item: &ItemDescription,
+) -> bool {
+    let source = ContentSource::Supplemental;
+    let preferred = precedence::prefers(order, source, *description_source);
+
+    if !preferred {
+        candidates.offer(source, &item.prose, false);
+        return false;
+    }
+
+    let mode = item.metadata.mode.unwrap_or_default();
+    let combined = mode.combine(description.as_deref(), &item.prose);
+
+    // The candidate records the description that's actually used. If it
+    // includes the lower-priority description, that one is still used too.
+    if mode != DescriptionMode::Replace && description.is_some() {
+        candidates.offer_combined(source, &combined);
+    } else {
+        candidates.offer(source, &combined, true);
+    }
+
+    *description = Some(combined);
+    *description_source = Some(ContentSource::Supplemental);
+    *description_origin = content.origin(item);
+    true
+}
+
+/// Looks up the types named by supplemental content, returning `None` if any
+/// of them isn't a known type.
+fn resolve_types<'a>(
+    types: &HashMap<String, DumpType>,
+    names: impl IntoIterator<Item = &'a String>,
+) -> Option<Vec<DumpType>> {
+    names
+        .into_iter()
+        .map(|name| types.get(name).cloned())
+        .collect()
+}
+
+/// Applies the supplemental metadata of each parameter. A `Type` replaces the
+/// dump's type only if the return type order prefers supplemental content and
+/// it names a known type; `check` reports the ones that don't.
+fn apply_parameter_metadata(
+    parameters: &mut [DumpFunctionParameter],
+    metadata: &Metadata,
+    types: &HashMap<String, DumpType>,
+    precedence: &Precedence,
+) {
+    let prefers_types = precedence::prefers(
+        &precedence.return_types,
+        ContentSource::Supplemental,
+        Some(ContentSource::ApiDump),
+    );
+
+    for parameter in parameters.iter_mut() {
+        if let Some(parameter_metadata) = metadata.parameters.get(&parameter.name) {
+            if let Some(description) = &parameter_metadata.description {
+                precedence::offer_description(
+                    &precedence.member_descriptions,
+                    &mut parameter.description,
+                    &mut parameter.description_source,
+                    &mut parameter.description_candidates,
+                    description,
+                    ContentSource::Supplemental,
+                );
+            }
+
+            if prefers_types {
+                if let Some(kind) = parameter_metadata
+                    .kind
+                    .as_ref()
+                    .and_then(|type_name| types.get(type_name))
+                {
+                    parameter.kind = kind.clone();
+                }
+            }
+
+            if let Some(default) = &parameter_metadata.default {
+                parameter.default = Some(default.clone());
+            }
+
+            if parameter_metadata.optional.is_some() {
+                parameter.optional = parameter_metadata.optional;
+            }
+        }
+    }
+}
+
+/// Replaces a return type from the dump with the supplemental `ReturnTypes`,
+/// if there are any, the order prefers them, and they all name known types.
+fn apply_return_types(
+    return_type: &mut DumpReturnType,
+    metadata: &Metadata,
+    types: &HashMap<String, DumpType>,
+    precedence: &Precedence,
+) {
+    let type_names = match &metadata.return_types {
+        Some(type_names) => type_names,
+        None => return,
+    };
+
+    let preferred = precedence::prefers(
+        &precedence.return_types,
+        ContentSource::Supplemental,
+        Some(ContentSource::ApiDump),
+    );
+
+    if preferred {
+        if let Some(resolved) = resolve_types(types, type_names) {
+            *return_type = DumpReturnType::Multiple(resolved);
+        }
+    }
+}
+
+/// Offers supplemental content as descriptions, and applies its parameter
+/// metadata and return types.
+pub fn apply_supplemental(dump: &mut Dump, content: &SupplementalData, precedence: &Precedence) {
+    let member_order = &precedence.member_descriptions;
+    let types = dump.named_types();
+
+    for class in dump.classes.iter_mut() {
+        if let Some(description) = content.find_item(&class.name) {
+            offer_supplemental_description(
+                &precedence.class_descriptions,
+                &mut class.description,
+                &mut class.description_source,
+                &mut class.description_origin,
+                &mut class.description_candidates,
+                content,
+                description,
+            );
+        }
+
+        for member in class.members.iter_mut() {
+            match member {
+                DumpClassMember::Function(function) => {
+                    if let Some(description) =
+                        content.find_item(&format!("{}.{}", &class.name, &function.name))
+                    {
+                        offer_supplemental_description(
+                            member_order,
+                            &mut function.description,
+                            &mut function.description_source,
+                            &mut function.description_origin,
+                            &mut function.description_candidates,
+                            content,
+                            description,
+                        );
+
+                        apply_parameter_metadata(
+                            &mut function.parameters,
+                            &description.metadata,
+                            &types,
+                            precedence,
+                        );
+                        apply_return_types(
+                            &mut function.return_type,
+                            &description.metadata,
+                            &types,
+                            precedence,
+                        );
+                    }
+                }
+                DumpClassMember::Property(property) => {
+                    if let Some(description) =
+                        content.find_item(&format!("{}.{}", &class.name, &property.name))
+                    {
+                        offer_supplemental_description(
+                            member_order,
+                            &mut property.description,
+                            &mut property.description_source,
+                            &mut property.description_origin,
+                            &mut property.description_candidates,
+                            content,
+                            description,
+                        );
+                    }
+                }
+                DumpClassMember::Event(event) => {
+                    if let Some(description) =
+                        content.find_item(&format!("{}.{}", &class.name, &event.name))
+                    {
+                        offer_supplemental_description(
+                            member_order,
+                            &mut event.description,
+                            &mut event.description_source,
+                            &mut event.description_origin,
+                            &mut event.description_candidates,
+                            content,
+                            description,
+                        );
+
+                        apply_parameter_metadata(
+                            &mut event.parameters,
+                            &description.metadata,
+                            &types,
+                            precedence,
+                        );
+                    }
+                }
+                DumpClassMember::Callback(callback) => {
+                    if let Some(description) =
+                        content.find_item(&format!("{}.{}", &class.name, &callback.name))
+                    {
+                        offer_supplemental_description(
+                            member_order,
+                            &mut callback.description,
+                            &mut callback.description_source,
+                            &mut callback.description_origin,
+                            &mut callback.description_candidates,
+                            content,
+                            description,
+                        );
+
+                        apply_parameter_metadata(
+                            &mut callback.parameters,
+                            &description.metadata,
+                            &types,
+                            precedence,
+                        );
+                        apply_return_types(
+                            &mut callback.return_type,
+                            &description.metadata,
+                            &types,
+                            precedence,
+                        );
+                    }
+                }
+                DumpClassMember::Unknown(_) => {}
+            }
+        }
+    }
+
+    for dump_enum in dump.enums.iter_mut() {
+        let enum_target = format!("Enum.{}", dump_enum.name);
+
+        if let Some(description) = content.find_item(&enum_target) {
+            offer_supplemental_description(
+                &precedence.class_descriptions,
+                &mut dump_enum.description,
+                &mut dump_enum.description_source,
+                &mut dump_enum.description_origin,
+                &mut dump_enum.description_candidates,
+                content,
+                description,
+            );
+        }
+
+        for item in dump_enum.items.iter_mut() {
+            if let Some(description) = content.find_item(&format!("{}.{}", enum_target, item.name))
+            {
+                offer_supplemental_description(
+                    member_order,
+                    &mut item.description,
+                    &mut item.description_source,
+                    &mut item.description_origin,
+                    &mut item.description_candidates,
+                    content,
+                    description,
+                );
+            }
+        }
+    }
+}
+
+/// Records when each class, member, and enum item was added and removed. The
+/// history should end with the dump being documented, added with
+/// `VersionHistory::push`, or items new in it won't be marked as added.
+pub fn apply_history(dump: &mut Dump, history: &VersionHistory) {
+    for class in dump.classes.iter_mut() {
+        class.history = history.get(&class.name);
+
+        for member in class.members.iter_mut() {
+            let target = format!("{}.{}", class.name, member.get_name());
+            member.set_history(history.get(&target));
+        }
+    }
+
+    for dump_enum in dump.enums.iter_mut() {
+        for item in dump_enum.items.iter_mut() {
+            item.history = history.get(&format!("Enum.{}.{}", dump_enum.name, item.name));
+        }
+    }
+}
+
+/// Offers DevHub class and property descriptions. Pages leave descriptions
+/// blank when nobody has written one, so blank descriptions aren't offered.
+pub fn apply_devhub(dump: &mut Dump, content: &DevHubData, precedence: &Precedence) {
+    for devhub_class in content.classes.values() {
+        if let Some(dump_class) = dump.find_class_mut(&devhub_class.name) {
+            if !devhub_class.description.trim().is_empty() {
+                precedence::offer_description(
+                    &precedence.class_descriptions,
+                    &mut dump_class.description,
+                    &mut dump_class.description_source,
+                    &mut dump_class.description_candidates,
+                    &devhub_class.description,
+                    ContentSource::DevHub,
+                );
+            }
+
+            for property in &devhub_class.properties {
+                if property.description.trim().is_empty() {
+                    continue;
+                }
+
+                if let Some(dump_member) = dump_class
+                    .properties_mut()
+                    .find(|item| item.name == property.name)
+                {
+                    precedence::offer_description(
+                        &precedence.member_descriptions,
+                        &mut dump_member.description,
+                        &mut dump_member.description_source,
+                        &mut dump_member.description_candidates,
+                        &property.description,
+                        ContentSource::DevHub,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{
+        dump::DumpClassFunction,
+        dump_devhub::{DevHubClass, DevHubProperty},
+        heuristics,
+    };
+
+    #[test]
+    fn reflection_metadata_tags() {
+        let mut dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Members": [
+                        {
+                            "MemberType": "Function",
+                            "Name": "Resize",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "resize",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Function",
+                            "Name": "BreakJoints",
+                            "Parameters": [],
+                            "ReturnType": { "Name": "null", "Category": "Primitive" },
+                            "Security": "None",
+                            "Tags": ["Deprecated"]
+                        }
+                    ]
+                }],
+                "Enums": []
+            }"#,
+        )
+        .unwrap();
+
+        let metadata = ReflectionMetadata::read_from_reader(
+            r#"<roblox version="4">
+<Item class="ReflectionMetadataClasses">
+  <Item class="ReflectionMetadataClass">
+    <Properties><string name="Name">Part</string><string name="Browsable">false</string></Properties>
+    <Item class="ReflectionMetadataFunctions">
+      <Item class="ReflectionMetadataMember">
+        <Properties><string name="Name">resize</string><string name="deprecated">true</string></Properties>
+      </Item>
+      <Item class="ReflectionMetadataMember">
+        <Properties><string name="Name">BreakJoints</string><string name="deprecated">true</string></Properties>
+      </Item>
+    </Item>
+  </Item>
+</Item>
+</roblox>"#
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let precedence = Precedence::default();
+        apply_reflection_metadata(&mut dump, &metadata, &precedence);
+        heuristics::superseded_members_probably_deprecated(&mut dump, &precedence);
+
+        let part = &dump.classes[0];
+        assert!(part.tags.contains("NotBrowsable"));
+        assert_eq!(
+            part.tag_sources.get("NotBrowsable"),
+            Some(&ContentSource::ReflectionMetadata)
+        );
+
+        let resize = match &part.members[1] {
+            DumpClassMember::Function(inner) => inner,
+            _ => unreachable!(),
+        };
+        assert!(resize.tags.contains("Deprecated"));
+        assert_eq!(
+            resize.tag_sources.get("Deprecated"),
+            Some(&ContentSource::ReflectionMetadata)
+        );
+
+        // Tags the dump already has aren't attributed to anything else.
+        let break_joints = match &part.members[2] {
+            DumpClassMember::Function(inner) => inner,
+            _ => unreachable!(),
+        };
+        assert!(break_joints.tag_sources.is_empty());
+
+        let mut dump_without_tags: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{ "Name": "Part", "Members": [] }],
+                "Enums": []
+            }"#,
+        )
+        .unwrap();
+        let precedence = Precedence {
+            tags: vec![ContentSource::Heuristic],
+            ..Precedence::default()
+        };
+        apply_reflection_metadata(&mut dump_without_tags, &metadata, &precedence);
+        assert!(dump_without_tags.classes[0].tags.is_empty());
+    }
+
+    #[test]
+    fn enum_descriptions() {
+        let mut dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [],
+                "Enums": [{
+                    "Name": "Material",
+                    "Items": [
+                        { "Name": "Plastic", "Value": 256 },
+                        { "Name": "Wood", "Value": 512 }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let metadata = ReflectionMetadata::read_from_reader(
+            r#"<roblox version="4">
+<Item class="ReflectionMetadataEnums">
+  <Item class="ReflectionMetadataEnum">
+    <Properties><string name="Name">Material</string><string name="summary">Official.</string><string name="deprecated">true</string></Properties>
+    <Item class="ReflectionMetadataEnumItem">
+      <Properties><string name="Name">Wood</string><string name="summary">Official wood.</string></Properties>
+    </Item>
+  </Item>
+</Item>
+</roblox>"#
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let content = SupplementalData::read_from_str(
+            "+++\nTarget = \"Enum.Material\"\n+++\nWhat a part is made of.\n\n\
+             +++\nTarget = \"Enum.Material.Plastic\"\n+++\nThe default material.\n",
+        )
+        .unwrap();
+
+        let precedence = Precedence::default();
+        apply_reflection_metadata(&mut dump, &metadata, &precedence);
+        apply_supplemental(&mut dump, &content, &precedence);
+
+        let material = &dump.enums[0];
+        assert_eq!(
+            material.description.as_deref(),
+            Some("What a part is made of.")
+        );
+        assert_eq!(
+            material.description_source,
+            Some(ContentSource::Supplemental)
+        );
+        assert_eq!(
+            material
+                .reflection_metadata
+                .as_ref()
+                .and_then(|attributes| attributes.deprecated),
+            Some(true)
+        );
+        assert!(material.tags.contains("Deprecated"));
+        assert_eq!(
+            material.tag_sources.get("Deprecated"),
+            Some(&ContentSource::ReflectionMetadata)
+        );
+
+        let plastic = &material.items[0];
+        assert_eq!(
+            plastic.description.as_deref(),
+            Some("The default material.")
+        );
+        assert_eq!(
+            plastic.description_source,
+            Some(ContentSource::Supplemental)
+        );
+
+        let wood = &material.items[1];
+        assert_eq!(wood.description.as_deref(), Some("Official wood."));
+        assert_eq!(
+            wood.description_source,
+            Some(ContentSource::ReflectionMetadata)
+        );
+    }
+
+    fn find_first_child_dump() -> Dump {
+        serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Instance",
+                    "Members": [{
+                        "MemberType": "Function",
+                        "Name": "FindFirstChild",
+                        "Parameters": [
+                            { "Name": "name", "Type": { "Name": "string", "Category": "Primitive" } },
+                            { "Name": "recursive", "Type": { "Name": "bool", "Category": "Primitive" } }
+                        ],
+                        "ReturnType": { "Name": "Instance", "Category": "Class" },
+                        "Security": "None"
+                    }]
+                }],
+                "Enums": [{ "Name": "Material", "Items": [] }]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn find_first_child(dump: &Dump) -> &DumpClassFunction {
+        dump.classes[0].functions().next().unwrap()
+    }
+
+    #[test]
+    fn parameter_metadata() {
+        let mut dump = find_first_child_dump();
+
+        let content = SupplementalData::read_from_str(
+            "+++\nTarget = \"Instance.FindFirstChild\"\nReturnTypes = [\"Instance\", \"Enum.Material\"]\n\n\
+             [Parameters.name]\nDescription = \"The name to look for.\"\nType = \"Enum.Material\"\n\n\
+             [Parameters.recursive]\nOptional = true\nDefault = \"false\"\nType = \"Banana\"\n+++\n\
+             Finds a child.\n",
+        )
+        .unwrap();
+
+        apply_supplemental(&mut dump, &content, &Precedence::default());
+
+        let function = find_first_child(&dump);
+
+        let name = &function.parameters[0];
+        assert_eq!(name.description.as_deref(), Some("The name to look for."));
+        assert_eq!(name.description_source, Some(ContentSource::Supplemental));
+        assert_eq!(
+            name.kind,
+            DumpType {
+                name: "Material".to_owned(),
+                category: "Enum".to_owned(),
+            }
+        );
+        assert_eq!(name.optional, None);
+
+        let recursive = &function.parameters[1];
+        assert_eq!(recursive.description, None);
+        assert_eq!(recursive.optional, Some(true));
+        assert_eq!(recursive.default.as_deref(), Some("false"));
+        assert_eq!(recursive.kind.name, "bool");
+        assert_eq!(recursive.kind.category, "Primitive");
+
+        let return_types: Vec<_> = function
+            .return_type
+            .types()
+            .iter()
+            .map(|dump_type| dump_type.category.as_str())
+            .collect();
+        assert_eq!(return_types, vec!["Class", "Enum"]);
+    }
+
+    #[test]
+    fn types_follow_precedence() {
+        let mut dump = find_first_child_dump();
+
+        let content = SupplementalData::read_from_str(
+            "+++\nTarget = \"Instance.FindFirstChild\"\nReturnTypes = [\"Enum.Material\"]\n\n\
+             [Parameters.name]\nType = \"Enum.Material\"\n+++\nFinds a child.\n",
+        )
+        .unwrap();
+
+        let precedence = Precedence {
+            return_types: vec![ContentSource::ApiDump, ContentSource::Supplemental],
+            ..Precedence::default()
+        };
+
+        apply_supplemental(&mut dump, &content, &precedence);
+
+        let function = find_first_child(&dump);
+        assert_eq!(function.parameters[0].kind.name, "string");
+        assert_eq!(function.return_type.types()[0].name, "Instance");
+    }
+
+    #[test]
+    fn stubs_are_skipped() {
+        let mut dump = find_first_child_dump();
+
+        let content = SupplementalData::read_from_str(
+            "+++\nTarget = \"Instance\"\nStub = true\n+++\nCopied summary.\n\n\
+             +++\nTarget = \"Instance.FindFirstChild\"\nStub = true\n\
+             # function Instance:FindFirstChild(name: string, recursive: bool): Instance\n+++\n",
+        )
+        .unwrap();
+
+        apply_supplemental(&mut dump, &content, &Precedence::default());
+
+        let instance = &dump.classes[0];
+        assert_eq!(instance.description, None);
+        assert_eq!(instance.description_candidates.alternatives().count(), 0);
+        assert_eq!(find_first_child(&dump).description, None);
+    }
+
+    #[test]
+    fn blank_devhub_descriptions_are_skipped() {
+        let mut dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Members": [{
+                        "MemberType": "Property",
+                        "Name": "Anchored",
+                        "ValueType": { "Name": "bool", "Category": "Primitive" },
+                        "Security": { "Read": "None", "Write": "None" },
+                        "Category": "Behavior"
+                    }]
+                }],
+                "Enums": []
+            }"#,
+        )
+        .unwrap();
+
+        let mut classes = HashMap::new();
+        classes.insert(
+            "Part".to_owned(),
+            DevHubClass {
+                name: "Part".to_owned(),
+                description: " \n".to_owned(),
+                properties: vec![DevHubProperty {
+                    name: "Anchored".to_owned(),
+                    description: String::new(),
+                }],
+            },
+        );
+
+        apply_devhub(&mut dump, &DevHubData { classes }, &Precedence::default());
+
+        let part = &dump.classes[0];
+        assert_eq!(part.description, None);
+        assert_eq!(part.description_candidates.alternatives().count(), 0);
+        assert_eq!(part.members[0].description(), None);
+    }
+
+    #[test]
+    fn combined_description_candidates() {
+        let mut dump = find_first_child_dump();
+
+        let metadata = ReflectionMetadata::read_from_reader(
+            r#"<roblox version="4">
+<Item class="ReflectionMetadataClasses">
+  <Item class="ReflectionMetadataClass">
+    <Properties><string name="Name">Instance</string><string name="summary">Official.</string></Properties>
+  </Item>
+</Item>
+</roblox>"#
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let content = SupplementalData::read_from_str(
+            "+++\nTarget = \"Instance\"\nMode = \"append\"\n+++\nMore.\n\n\
+             +++\nTarget = \"Instance.FindFirstChild\"\n\n\
+             [Parameters.name]\nDescription = \"The name to look for.\"\n+++\nFinds a child.\n",
+        )
+        .unwrap();
+
+        let precedence = Precedence::default();
+        apply_reflection_metadata(&mut dump, &metadata, &precedence);
+        apply_supplemental(&mut dump, &content, &precedence);
+
+        let instance = &dump.classes[0];
+        assert_eq!(instance.description.as_deref(), Some("Official.\n\nMore."));
+        assert_eq!(instance.description_candidates.alternatives().count(), 0);
+
+        let parameter = &find_first_child(&dump).parameters[0];
+        assert_eq!(
+            parameter.description.as_deref(),
+            Some("The name to look for.")
+        );
+        assert_eq!(parameter.description_candidates.alternatives().count(), 0);
+
+        let serialized = serde_json::to_value(&parameter.description_candidates).unwrap();
+        assert_eq!(serialized[0]["Description"], "The name to look for.");
+        assert_eq!(serialized[0]["Chosen"], true);
+    }
+}
@@ -1,14 +1,14 @@
-use std::{
-    collections::BTreeSet,
-    fmt::{self, Write},
-};
+//! Renders a dump as a single, self-contained HTML page.
+
+use std::fmt::{self, Write};
 
-use pulldown_cmark;
 use ritz::{html, Fragment, HtmlContent, UnescapedText};
 
 use crate::dump::{
-    ContentSource, Dump, DumpClass, DumpClassCallback, DumpClassEvent, DumpClassFunction,
-    DumpClassProperty, DumpFunctionParameter, DumpReturnType,
+    ContentSource, DescriptionCandidates, DescriptionOrigin, Dump, DumpClass, DumpClassCallback,
+    DumpClassEvent, DumpClassFunction, DumpClassMember, DumpClassProperty, DumpClassUnknownMember,
+    DumpEnum, DumpEnumItem, DumpFunctionParameter, DumpItemHistory, DumpReturnType, DumpTags,
+    DumpType,
 };
 
 static STYLE: &str = include_str!(concat!(
@@ -18,7 +18,7 @@ static STYLE: &str = include_str!(concat!(
 
 static DEFAULT_DESCRIPTION: &str = "*No description available.*";
 
-fn render_markdown(input: &str) -> HtmlContent {
+fn render_markdown(input: &str) -> HtmlContent<'_> {
     let parser = pulldown_cmark::Parser::new(input);
     let mut output = String::new();
     pulldown_cmark::html::push_html(&mut output, parser);
@@ -26,7 +26,17 @@ fn render_markdown(input: &str) -> HtmlContent {
     UnescapedText::new(output).into()
 }
 
-pub fn emit_wiki(dump: &Dump, output: &mut String) -> fmt::Result {
+/// Settings for `emit_wiki` beyond the dump itself.
+#[derive(Debug, Default)]
+pub struct WikiOptions<'a> {
+    /// A URL template used to link each description from supplemental content
+    /// to its source, like
+    /// `https://github.com/rodocs/docs/blob/master/content/{path}#L{line}-L{end_line}`.
+    pub edit_url_template: Option<&'a str>,
+}
+
+/// Writes the whole wiki page, including its styles, to `output`.
+pub fn emit_wiki(dump: &Dump, options: &WikiOptions, output: &mut String) -> fmt::Result {
     writeln!(output, "<!doctype html>")?;
 
     let html = html!(
@@ -39,7 +49,10 @@ pub fn emit_wiki(dump: &Dump, output: &mut String) -> fmt::Result {
             </head>
             <body>
                 <div class="dump-classes">
-                    { Fragment::new(dump.classes.iter().map(render_class)) }
+                    { Fragment::new(dump.classes.iter().map(|class| render_class(dump, class, options))) }
+                </div>
+                <div class="dump-classes dump-enums">
+                    { Fragment::new(dump.enums.iter().map(|dump_enum| render_enum(dump_enum, options))) }
                 </div>
             </body>
         </html>
@@ -48,13 +61,101 @@ pub fn emit_wiki(dump: &Dump, output: &mut String) -> fmt::Result {
     write!(output, "{}", html)
 }
 
-fn render_class(class: &DumpClass) -> HtmlContent {
-    let description = class
+fn render_enum<'a>(dump_enum: &'a DumpEnum, options: &WikiOptions) -> HtmlContent<'a> {
+    let description = dump_enum
         .description
-        .as_ref()
-        .map(String::as_str)
+        .as_deref()
         .unwrap_or(DEFAULT_DESCRIPTION);
 
+    let qualified_name = format!("Enum.{}", dump_enum.name);
+
+    let mut element_class = "dump-class dump-enum".to_owned();
+    if dump_enum.tags.contains("Deprecated") {
+        element_class.push_str(" dump-class-deprecated");
+    }
+
+    html!(
+        <div id={ qualified_name.clone() } class={ element_class }>
+            <a class="dump-class-title" href={ format!("#{}", qualified_name) }>
+                { qualified_name.clone() }
+            </a>
+
+            { render_tag_list(&dump_enum.tags, "dump-class-tags") }
+
+            <div class="dump-class-description">
+                <div class="dump-class-description-text markdown">
+                    { render_markdown(description) }
+                </div>
+                <div class="dump-class-description-meta">
+                    { dump_enum.description_source.map(|source| html!(
+                        <span class="dump-info" title={ format!("Content source: {}", source) } />
+                    )) }
+                    { render_edit_link(dump_enum.description_origin.as_ref(), options) }
+                </div>
+                { render_description_alternatives(&dump_enum.description_candidates) }
+            </div>
+
+            <div class="dump-class-member-section">
+                <div class="dump-class-subtitle">"Items"</div>
+                <div class="dump-class-member-section-list">
+                    { Fragment::new(dump_enum.items.iter().map(|item| render_enum_item(item, &qualified_name, options))) }
+                </div>
+            </div>
+        </div>
+    )
+}
+
+fn render_enum_item<'a>(
+    item: &'a DumpEnumItem,
+    parent_name: &str,
+    options: &WikiOptions,
+) -> HtmlContent<'a> {
+    let description = item.description.as_deref().unwrap_or(DEFAULT_DESCRIPTION);
+
+    let qualified_name = format!("{}.{}", parent_name, item.name);
+
+    html!(
+        <div class={ member_element_class(&item.tags, "dump-enum-item") } id={ qualified_name.clone() }>
+            <div class="dump-enum-item-signature">
+                <a class="dump-class-member-name" href={ format!("#{}", qualified_name) }>
+                    { &item.name }
+                </a>
+                " = "
+                { item.value.to_string() }
+                { render_history(item.history.as_ref()) }
+            </div>
+            { render_tag_list(&item.tags, "dump-enum-item-tags") }
+            { render_member_description(description, item.description_source, render_edit_link(item.description_origin.as_ref(), options), render_description_alternatives(&item.description_candidates)) }
+        </div>
+    )
+}
+
+fn render_tag_list<'a>(tags: &DumpTags, element_class: &'a str) -> HtmlContent<'a> {
+    if tags.is_empty() {
+        return HtmlContent::None;
+    }
+
+    let tags = tags
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    html!(
+        <p class={ element_class }>
+            "Tags: "
+            { tags }
+        </p>
+    )
+}
+
+fn render_class<'a>(
+    dump: &'a Dump,
+    class: &'a DumpClass,
+    options: &WikiOptions,
+) -> HtmlContent<'a> {
+    let description = class.description.as_deref().unwrap_or(DEFAULT_DESCRIPTION);
+
     let mut element_class = "dump-class".to_owned();
     if class.tags.contains("Deprecated") {
         element_class.push_str(" dump-class-deprecated");
@@ -66,6 +167,8 @@ fn render_class(class: &DumpClass) -> HtmlContent {
                 { &class.name }
             </a>
 
+            { render_history(class.history.as_ref()) }
+
             { class.superclass.as_ref().map(|superclass| html!(
                 <p class="dump-class-inherits">
                     "Inherits: "
@@ -73,16 +176,9 @@ fn render_class(class: &DumpClass) -> HtmlContent {
                 </p>
             )) }
 
-            { if class.tags.len() > 0 {
-                html!(
-                     <p class="dump-class-tags">
-                        "Tags: "
-                        { class.tags.iter().map(|value| value.as_str()).collect::<Vec<_>>().join(", ")}
-                     </p>
-                )
-            } else {
-                HtmlContent::None
-            }}
+            { render_tag_list(&class.tags, "dump-class-tags") }
+
+            { render_preferred_replacement(class.preferred_replacement(), None) }
 
             <div class="dump-class-description">
                 <div class="dump-class-description-text markdown">
@@ -92,7 +188,9 @@ fn render_class(class: &DumpClass) -> HtmlContent {
                     { class.description_source.map(|source| html!(
                         <span class="dump-info" title={ format!("Content source: {}", source) } />
                     )) }
+                    { render_edit_link(class.description_origin.as_ref(), options) }
                 </div>
+                { render_description_alternatives(&class.description_candidates) }
             </div>
 
             { if class.has_properties() {
@@ -100,7 +198,7 @@ fn render_class(class: &DumpClass) -> HtmlContent {
                     <div class="dump-class-member-section">
                         <div class="dump-class-subtitle">"Properties"</div>
                         <div class="dump-class-member-section-list">
-                            { Fragment::new(class.properties().map(|property| render_property(property, &class.name))) }
+                            { Fragment::new(class.properties().map(|property| render_property(property, &class.name, options))) }
                         </div>
                     </div>
                 )
@@ -113,7 +211,7 @@ fn render_class(class: &DumpClass) -> HtmlContent {
                     <div class="dump-class-member-section">
                         <div class="dump-class-subtitle">"Functions"</div>
                         <div class="dump-class-member-section-list">
-                            { Fragment::new(class.functions().map(|function| render_function(function, &class.name))) }
+                            { Fragment::new(class.functions().map(|function| render_function(function, &class.name, options))) }
                         </div>
                     </div>
                 )
@@ -126,7 +224,7 @@ fn render_class(class: &DumpClass) -> HtmlContent {
                     <div class="dump-class-member-section">
                         <div class="dump-class-subtitle">"Events"</div>
                         <div class="dump-class-member-section-list">
-                            { Fragment::new(class.events().map(|event| render_event(event, &class.name))) }
+                            { Fragment::new(class.events().map(|event| render_event(event, &class.name, options))) }
                         </div>
                     </div>
                 )
@@ -139,22 +237,147 @@ fn render_class(class: &DumpClass) -> HtmlContent {
                     <div class="dump-class-member-section">
                         <div class="dump-class-subtitle">"Callbacks"</div>
                         <div class="dump-class-member-section-list">
-                            { Fragment::new(class.callbacks().map(|callback| render_callback(callback, &class.name))) }
+                            { Fragment::new(class.callbacks().map(|callback| render_callback(callback, &class.name, options))) }
+                        </div>
+                    </div>
+                )
+            } else {
+                HtmlContent::None
+            } }
+
+            { if class.has_unknown_members() {
+                html!(
+                    <div class="dump-class-member-section">
+                        <div class="dump-class-subtitle">"Other Members"</div>
+                        <div class="dump-class-member-section-list">
+                            { Fragment::new(class.unknown_members().map(|member| render_unknown_member(member, &class.name))) }
                         </div>
                     </div>
                 )
             } else {
                 HtmlContent::None
             } }
+
+            { render_inherited_members(dump, class) }
+        </div>
+    )
+}
+
+/// Lists the members a class inherits, grouped by the ancestor that declares
+/// them, so readers don't have to follow every "Inherits" link themselves.
+fn render_inherited_members<'a>(dump: &'a Dump, class: &'a DumpClass) -> HtmlContent<'a> {
+    let inherited: Vec<_> = dump
+        .all_members(&class.name)
+        .filter(|(declaring_class, _)| declaring_class.name != class.name)
+        .collect();
+
+    if inherited.is_empty() {
+        return HtmlContent::None;
+    }
+
+    let groups = dump.ancestors(&class.name).filter_map(|ancestor| {
+        let members: Vec<&DumpClassMember> = inherited
+            .iter()
+            .filter(|(declaring_class, _)| declaring_class.name == ancestor.name)
+            .map(|(_, member)| *member)
+            .collect();
+
+        if members.is_empty() {
+            return None;
+        }
+
+        let kinds = [
+            ("Properties", "Property"),
+            ("Functions", "Function"),
+            ("Events", "Event"),
+            ("Callbacks", "Callback"),
+        ];
+
+        let mut lists = Vec::new();
+
+        for (title, member_type) in &kinds {
+            let of_kind: Vec<_> = members
+                .iter()
+                .filter(|member| member.member_type() == *member_type)
+                .cloned()
+                .collect();
+
+            if !of_kind.is_empty() {
+                lists.push(render_inherited_member_list(title, &ancestor.name, of_kind));
+            }
+        }
+
+        let others: Vec<_> = members
+            .iter()
+            .filter(|member| !kinds.iter().any(|(_, kind)| member.member_type() == *kind))
+            .cloned()
+            .collect();
+
+        if !others.is_empty() {
+            lists.push(render_inherited_member_list(
+                "Other Members",
+                &ancestor.name,
+                others,
+            ));
+        }
+
+        Some(html!(
+            <div class="dump-class-inherited-group">
+                <div class="dump-class-inherited-title">
+                    "From "
+                    { render_type_link(&ancestor.name) }
+                </div>
+                { Fragment::new(lists) }
+            </div>
+        ))
+    });
+
+    html!(
+        <div class="dump-class-member-section">
+            <div class="dump-class-subtitle">"Inherited Members"</div>
+            <div class="dump-class-member-section-list">
+                { Fragment::new(groups.collect::<Vec<_>>()) }
+            </div>
         </div>
     )
 }
 
-fn render_property<'a>(property: &'a DumpClassProperty, parent_name: &str) -> HtmlContent<'a> {
+fn render_inherited_member_list<'a>(
+    title: &'a str,
+    declaring_class: &'a str,
+    members: Vec<&'a DumpClassMember>,
+) -> HtmlContent<'a> {
+    let count = members.len();
+
+    html!(
+        <p class="dump-class-inherited-list">
+            <span class="dump-class-inherited-kind">{ title }": "</span>
+            { Fragment::new(members.into_iter().enumerate().map(move |(index, member)| html!(
+                <span>
+                    <a href={ format!("#{}.{}", declaring_class, member.get_name()) }>
+                        { member.get_name() }
+                    </a>
+                    {
+                        if index < count - 1 {
+                            ", ".into()
+                        } else {
+                            HtmlContent::None
+                        }
+                    }
+                </span>
+            ))) }
+        </p>
+    )
+}
+
+fn render_property<'a>(
+    property: &'a DumpClassProperty,
+    parent_name: &str,
+    options: &WikiOptions,
+) -> HtmlContent<'a> {
     let description = property
         .description
-        .as_ref()
-        .map(String::as_str)
+        .as_deref()
         .unwrap_or(DEFAULT_DESCRIPTION);
 
     let qualified_name = format!("{}.{}", parent_name, property.name);
@@ -166,18 +389,23 @@ fn render_property<'a>(property: &'a DumpClassProperty, parent_name: &str) -> Ht
                     { &property.name }
                 </a>
                 ": "
-                { render_type_link(&property.value_type.name) }
+                { render_dump_type_link(&property.value_type) }
+                { render_history(property.history.as_ref()) }
             </div>
-            { render_member_description(description, property.description_source) }
+            { render_preferred_replacement(property.tags.preferred_descriptor_name(), Some(parent_name)) }
+            { render_member_description(description, property.description_source, render_edit_link(property.description_origin.as_ref(), options), render_description_alternatives(&property.description_candidates)) }
         </div>
     )
 }
 
-fn render_function<'a>(function: &'a DumpClassFunction, parent_name: &str) -> HtmlContent<'a> {
+fn render_function<'a>(
+    function: &'a DumpClassFunction,
+    parent_name: &str,
+    options: &WikiOptions,
+) -> HtmlContent<'a> {
     let description = function
         .description
-        .as_ref()
-        .map(String::as_str)
+        .as_deref()
         .unwrap_or(DEFAULT_DESCRIPTION);
 
     let qualified_name = format!("{}.{}", parent_name, function.name);
@@ -192,18 +420,21 @@ fn render_function<'a>(function: &'a DumpClassFunction, parent_name: &str) -> Ht
                 { render_arguments(&function.parameters) }
                 ") => "
                 { render_return_type(&function.return_type) }
+                { render_history(function.history.as_ref()) }
             </div>
-            { render_member_description(description, function.description_source) }
+            { render_preferred_replacement(function.tags.preferred_descriptor_name(), Some(parent_name)) }
+            { render_member_description(description, function.description_source, render_edit_link(function.description_origin.as_ref(), options), render_description_alternatives(&function.description_candidates)) }
+            { render_parameter_descriptions(&function.parameters) }
         </div>
     )
 }
 
-fn render_event<'a>(event: &'a DumpClassEvent, parent_name: &str) -> HtmlContent<'a> {
-    let description = event
-        .description
-        .as_ref()
-        .map(String::as_str)
-        .unwrap_or(DEFAULT_DESCRIPTION);
+fn render_event<'a>(
+    event: &'a DumpClassEvent,
+    parent_name: &str,
+    options: &WikiOptions,
+) -> HtmlContent<'a> {
+    let description = event.description.as_deref().unwrap_or(DEFAULT_DESCRIPTION);
 
     let qualified_name = format!("{}.{}", parent_name, event.name);
 
@@ -218,17 +449,23 @@ fn render_event<'a>(event: &'a DumpClassEvent, parent_name: &str) -> HtmlContent
                 "("
                 { render_arguments(&event.parameters) }
                 ")"
+                { render_history(event.history.as_ref()) }
             </div>
-            { render_member_description(description, event.description_source) }
+            { render_preferred_replacement(event.tags.preferred_descriptor_name(), Some(parent_name)) }
+            { render_member_description(description, event.description_source, render_edit_link(event.description_origin.as_ref(), options), render_description_alternatives(&event.description_candidates)) }
+            { render_parameter_descriptions(&event.parameters) }
         </div>
     )
 }
 
-fn render_callback<'a>(callback: &'a DumpClassCallback, parent_name: &str) -> HtmlContent<'a> {
+fn render_callback<'a>(
+    callback: &'a DumpClassCallback,
+    parent_name: &str,
+    options: &WikiOptions,
+) -> HtmlContent<'a> {
     let description = callback
         .description
-        .as_ref()
-        .map(String::as_str)
+        .as_deref()
         .unwrap_or(DEFAULT_DESCRIPTION);
 
     let qualified_name = format!("{}.{}", parent_name, callback.name);
@@ -243,13 +480,41 @@ fn render_callback<'a>(callback: &'a DumpClassCallback, parent_name: &str) -> Ht
                 { render_arguments(&callback.parameters) }
                 ") => "
                 { render_return_type(&callback.return_type) }
+                { render_history(callback.history.as_ref()) }
+            </div>
+            { render_preferred_replacement(callback.tags.preferred_descriptor_name(), Some(parent_name)) }
+            { render_member_description(description, callback.description_source, render_edit_link(callback.description_origin.as_ref(), options), render_description_alternatives(&callback.description_candidates)) }
+            { render_parameter_descriptions(&callback.parameters) }
+        </div>
+    )
+}
+
+fn render_unknown_member<'a>(
+    member: &'a DumpClassUnknownMember,
+    parent_name: &str,
+) -> HtmlContent<'a> {
+    let qualified_name = format!("{}.{}", parent_name, member.name);
+
+    html!(
+        <div class={ member_element_class(&member.tags, "dump-class-unknown-member") } id={ qualified_name.clone() }>
+            <div class="dump-function-signature">
+                <a class="dump-class-member-name" href={ format!("#{}", qualified_name)}>
+                    { &member.name }
+                </a>
+                ": "
+                { &member.member_type }
             </div>
-            { render_member_description(description, callback.description_source) }
+            { render_member_description(DEFAULT_DESCRIPTION, None, HtmlContent::None, HtmlContent::None) }
         </div>
     )
 }
 
-fn render_member_description(description: &str, source: Option<ContentSource>) -> HtmlContent {
+fn render_member_description<'a>(
+    description: &'a str,
+    source: Option<ContentSource>,
+    edit_link: HtmlContent<'a>,
+    alternatives: HtmlContent<'a>,
+) -> HtmlContent<'a> {
     html!(
         <div class="dump-class-member-description">
             <div class="dump-class-member-description-text markdown">
@@ -259,14 +524,110 @@ fn render_member_description(description: &str, source: Option<ContentSource>) -
                 { Fragment::new(source.map(|source| html!(
                     <span class="dump-info" title={ format!("Content source: {}", source) } />
                 ))) }
+                { edit_link }
             </div>
+            { alternatives }
         </div>
     )
 }
 
-fn render_return_type(return_type: &DumpReturnType) -> HtmlContent {
+/// Lists the descriptions that content sources offered but that lost out to
+/// the one shown, so that writers can compare them.
+fn render_description_alternatives(candidates: &DescriptionCandidates) -> HtmlContent<'_> {
+    let alternatives: Vec<_> = candidates.alternatives().collect();
+
+    if alternatives.is_empty() {
+        return HtmlContent::None;
+    }
+
+    html!(
+        <details class="dump-description-alternatives">
+            <summary>{ format!("Other descriptions ({})", alternatives.len()) }</summary>
+            { Fragment::new(alternatives.into_iter().map(|candidate| html!(
+                <div class="dump-description-alternative">
+                    <div class="dump-description-alternative-source">{ candidate.source.to_string() }</div>
+                    <div class="markdown">{ render_markdown(&candidate.description) }</div>
+                </div>
+            ))) }
+        </details>
+    )
+}
+
+/// Links a description to the supplemental content it was written in, if the
+/// wiki was given a URL template to build links with.
+fn render_edit_link<'a>(
+    origin: Option<&DescriptionOrigin>,
+    options: &WikiOptions,
+) -> HtmlContent<'a> {
+    let (origin, template) = match (origin, options.edit_url_template) {
+        (Some(origin), Some(template)) => (origin, template),
+        _ => return HtmlContent::None,
+    };
+
+    let url = template
+        .replace("{path}", &origin.path)
+        .replace("{line}", &origin.start_line.to_string())
+        .replace("{end_line}", &origin.end_line.to_string());
+
+    html!(
+        <a class="dump-edit-link" href={ url } title={ format!("{}:{}", origin.path, origin.start_line) }>
+            "Edit this description"
+        </a>
+    )
+}
+
+/// Points readers at the item the dump says to use instead of this one.
+/// Member replacements are linked relative to their parent class.
+fn render_preferred_replacement<'a>(
+    preferred_name: Option<&'a str>,
+    parent_name: Option<&str>,
+) -> HtmlContent<'a> {
+    let preferred_name = match preferred_name {
+        Some(preferred_name) => preferred_name,
+        None => return HtmlContent::None,
+    };
+
+    let anchor = match parent_name {
+        Some(parent_name) if !preferred_name.contains('.') => {
+            format!("#{}.{}", parent_name, preferred_name)
+        }
+        _ => format!("#{}", preferred_name),
+    };
+
+    html!(
+        <p class="dump-preferred-replacement">
+            "Use "
+            <a href={ anchor }>{ preferred_name }</a>
+            " instead."
+        </p>
+    )
+}
+
+fn render_history(history: Option<&DumpItemHistory>) -> HtmlContent<'_> {
+    let history = match history {
+        Some(history) => history,
+        None => return HtmlContent::None,
+    };
+
+    html!(
+        <span class="dump-history">
+            { history.added_in.as_ref().map(|version| html!(
+                <span class="dump-badge dump-badge-added">
+                    { format!("Added in {}", version) }
+                </span>
+            )) }
+            { history.removed_in.as_ref().map(|version| html!(
+                <span class="dump-badge dump-badge-removed">
+                    { format!("Removed in {}", version) }
+                </span>
+            )) }
+        </span>
+    )
+}
+
+fn render_return_type(return_type: &DumpReturnType) -> HtmlContent<'_> {
     match return_type {
-        DumpReturnType::Single(t) => render_type_link(&t.name),
+        DumpReturnType::Single(t) => render_dump_type_link(t),
         DumpReturnType::Multiple(ts) => html!(
             <span>
             "("
@@ -276,7 +637,7 @@ fn render_return_type(return_type: &DumpReturnType) -> HtmlContent {
                 .enumerate()
                 .map(|(index, param)| html!(
                     <span class="dump-function-return-type">
-                        { render_type_link(&param.name) }
+                        { render_dump_type_link(param) }
                         {
                             if index < ts.len() - 1 {
                                 ", ".into()
@@ -293,7 +654,21 @@ fn render_return_type(return_type: &DumpReturnType) -> HtmlContent {
     }
 }
 
-fn render_type_link(name: &str) -> HtmlContent {
+/// Links to a type from the dump. Enums live under `Enum.` so that they can't
+/// collide with classes of the same name.
+fn render_dump_type_link(dump_type: &DumpType) -> HtmlContent<'_> {
+    if dump_type.category == "Enum" {
+        html!(
+            <a href={ format!("#Enum.{}", dump_type.name) }>
+                { &dump_type.name }
+            </a>
+        )
+    } else {
+        render_type_link(&dump_type.name)
+    }
+}
+
+fn render_type_link(name: &str) -> HtmlContent<'_> {
     html!(
         <a href={ format!("#{}", name) }>
             { name }
@@ -301,13 +676,15 @@ fn render_type_link(name: &str) -> HtmlContent {
     )
 }
 
-fn render_arguments(parameters: &[DumpFunctionParameter]) -> Fragment {
+fn render_arguments(parameters: &[DumpFunctionParameter]) -> Fragment<'_> {
     Fragment::new(parameters.iter().enumerate().map(|(index, param)| {
         html!(
             <div class="dump-function-argument">
                 { &param.name }
+                { if param.optional == Some(true) { "?".into() } else { HtmlContent::None } }
                 ": "
-                { render_type_link(&param.kind.name) }
+                { render_dump_type_link(&param.kind) }
+                { Fragment::new(param.default.as_ref().map(|default| format!(" = {}", default))) }
                 {
                     if index < parameters.len() - 1 {
                         ",".into()
@@ -320,7 +697,37 @@ fn render_arguments(parameters: &[DumpFunctionParameter]) -> Fragment {
     }))
 }
 
-fn member_element_class(tags: &BTreeSet<String>, main_class: &str) -> String {
+fn render_parameter_descriptions(parameters: &[DumpFunctionParameter]) -> HtmlContent<'_> {
+    let documented: Vec<_> = parameters
+        .iter()
+        .filter_map(|param| {
+            param
+                .description
+                .as_ref()
+                .map(|description| (param, description))
+        })
+        .collect();
+
+    if documented.is_empty() {
+        return HtmlContent::None;
+    }
+
+    html!(
+        <div class="dump-function-parameters">
+            <div class="dump-function-parameters-title">"Parameters"</div>
+            { Fragment::new(documented.into_iter().map(|(param, description)| html!(
+                <div class="dump-function-parameter">
+                    <div class="dump-function-parameter-name">{ &param.name }</div>
+                    <div class="dump-function-parameter-description markdown">
+                        { render_markdown(description) }
+                    </div>
+                </div>
+            ))) }
+        </div>
+    )
+}
+
+fn member_element_class(tags: &DumpTags, main_class: &str) -> String {
     let mut element_class = "dump-class-member ".to_owned();
     element_class.push_str(main_class);
     if tags.contains("Deprecated") {
@@ -0,0 +1,203 @@
+//! Decides which content source wins when several of them have something to
+//! say about the same field.
+//!
+//! Precedence is configured with a TOML file listing sources from highest to
+//! lowest priority for each field:
+//!
+//! ```toml
+//! ClassDescriptions = ["Supplemental", "ReflectionMetadata"]
+//! MemberDescriptions = ["Supplemental", "Heuristic", "ReflectionMetadata"]
+//! Tags = ["Heuristic"]
+//! ReturnTypes = ["Supplemental", "ApiDump"]
+//! ```
+//!
+//! Sources left out of a list are ignored for that field. Data from the JSON
+//! API dump itself, like its own tags and return types, is always kept as a
+//! fallback.
+//!
+//! `Tags` is the exception to ordering: tags are a union, so every listed
+//! source adds its tags and none of them can remove another's. The list only
+//! decides which sources are used, not which one wins.
+
+use std::{fmt, fs, io, path::Path};
+
+use serde_derive::Deserialize;
+
+use crate::dump::{ContentSource, DescriptionCandidates, DumpClassMember};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct Precedence {
+    /// Descriptions of classes and enums.
+    pub class_descriptions: Vec<ContentSource>,
+
+    /// Descriptions of members, parameters, and enum items.
+    pub member_descriptions: Vec<ContentSource>,
+
+    /// The sources that may add tags. Unlike the other fields this isn't a
+    /// precedence order: an item ends up with the union of the tags from
+    /// every listed source.
+    pub tags: Vec<ContentSource>,
+
+    /// Function and callback return types, and parameter types.
+    pub return_types: Vec<ContentSource>,
+}
+
+impl Default for Precedence {
+    fn default() -> Precedence {
+        let descriptions = vec![
+            ContentSource::Supplemental,
+            ContentSource::DevHub,
+            ContentSource::Heuristic,
+            ContentSource::ReflectionMetadata,
+        ];
+
+        Precedence {
+            class_descriptions: descriptions.clone(),
+            member_descriptions: descriptions,
+            tags: vec![ContentSource::Heuristic, ContentSource::ReflectionMetadata],
+            return_types: vec![ContentSource::Supplemental, ContentSource::ApiDump],
+        }
+    }
+}
+
+impl Precedence {
+    pub fn read_from_file(path: &Path) -> Result<Precedence, PrecedenceReadError> {
+        let contents = fs::read_to_string(path)?;
+
+        Ok(toml::from_str(&contents)?)
+    }
+}
+
+/// Returns whether a value from `source` should take the place of the current
+/// value, which came from `current`, under the given order.
+pub fn prefers(
+    order: &[ContentSource],
+    source: ContentSource,
+    current: Option<ContentSource>,
+) -> bool {
+    let rank = |source| order.iter().position(|item| *item == source);
+
+    let source_rank = match rank(source) {
+        Some(source_rank) => source_rank,
+        None => return false,
+    };
+
+    match current.and_then(rank) {
+        Some(current_rank) => source_rank <= current_rank,
+        None => true,
+    }
+}
+
+/// Replaces a description with one from `source` if the order prefers it over
+/// the current description, returning whether it did. Either way, the offered
+/// description is recorded as a candidate.
+pub fn offer_description(
+    order: &[ContentSource],
+    description: &mut Option<String>,
+    description_source: &mut Option<ContentSource>,
+    candidates: &mut DescriptionCandidates,
+    new_description: &str,
+    source: ContentSource,
+) -> bool {
+    let preferred = prefers(order, source, *description_source);
+
+    candidates.offer(source, new_description, preferred);
+
+    if preferred {
+        *description = Some(new_description.to_owned());
+        *description_source = Some(source);
+    }
+
+    preferred
+}
+
+/// Like `offer_description`, but for any kind of member.
+pub fn offer_member_description(
+    order: &[ContentSource],
+    member: &mut DumpClassMember,
+    description: &str,
+    source: ContentSource,
+) -> bool {
+    let preferred = prefers(order, source, member.description_source());
+
+    if preferred {
+        member.set_description(description.to_owned(), source);
+    } else {
+        member.reject_description(description, source);
+    }
+
+    preferred
+}
+
+#[derive(Debug)]
+pub enum PrecedenceReadError {
+    Io(io::Error),
+    InvalidToml(toml::de::Error),
+}
+
+impl fmt::Display for PrecedenceReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrecedenceReadError::Io(error) => write!(formatter, "{}", error),
+            PrecedenceReadError::InvalidToml(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl From<io::Error> for PrecedenceReadError {
+    fn from(error: io::Error) -> PrecedenceReadError {
+        PrecedenceReadError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for PrecedenceReadError {
+    fn from(error: toml::de::Error) -> PrecedenceReadError {
+        PrecedenceReadError::InvalidToml(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ranking() {
+        let order = [
+            ContentSource::ReflectionMetadata,
+            ContentSource::Supplemental,
+        ];
+
+        assert!(prefers(&order, ContentSource::Supplemental, None));
+        assert!(!prefers(
+            &order,
+            ContentSource::Supplemental,
+            Some(ContentSource::ReflectionMetadata)
+        ));
+        assert!(prefers(
+            &order,
+            ContentSource::ReflectionMetadata,
+            Some(ContentSource::Supplemental)
+        ));
+        assert!(!prefers(&order, ContentSource::Heuristic, None));
+    }
+
+    #[test]
+    fn partial_config() {
+        let precedence: Precedence =
+            toml::from_str(r#"MemberDescriptions = ["ReflectionMetadata", "Supplemental"]"#)
+                .unwrap();
+
+        assert_eq!(
+            precedence.member_descriptions,
+            vec![
+                ContentSource::ReflectionMetadata,
+                ContentSource::Supplemental
+            ]
+        );
+        assert_eq!(
+            precedence.class_descriptions,
+            Precedence::default().class_descriptions
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! Reads `dumpling.toml`, a project file that declares everything needed to
+//! build documentation: the inputs, which content sources and heuristics to
+//! use, and where each output goes.
+//!
+//! ```toml
+//! [Inputs]
+//! Dump = "api-dump.json"
+//! Metadata = "ReflectionMetadata.xml"
+//! Content = "content"
+//! History = "history"
+//!
+//! [Sources]
+//! DevHub = false
+//!
+//! [Heuristics]
+//! CamelCaseDeprecated = true
+//!
+//! [Precedence]
+//! MemberDescriptions = ["Supplemental", "ReflectionMetadata"]
+//!
+//! [Outputs.Miniwiki]
+//! Output = "build/miniwiki.html"
+//! EditUrl = "https://github.com/rodocs/docs/blob/master/content/{path}#L{line}"
+//!
+//! [Outputs.Megadump]
+//! Output = "build/megadump.json"
+//! Inheritance = true
+//! ```
+//!
+//! Every table is optional. Paths are relative to the directory containing
+//! `dumpling.toml`.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_derive::Deserialize;
+
+use crate::{
+    dump::Dump,
+    dump_devhub::DevHubData,
+    error::{Error, ErrorKind, Stage},
+    heuristics,
+    history::VersionHistory,
+    merge,
+    precedence::Precedence,
+    reflection_metadata::ReflectionMetadata,
+    supplement::SupplementalData,
+};
+
+pub static PROJECT_FILE_NAME: &str = "dumpling.toml";
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct Project {
+    pub inputs: ProjectInputs,
+    pub sources: ProjectSources,
+    pub heuristics: ProjectHeuristics,
+    pub precedence: Precedence,
+    pub outputs: ProjectOutputs,
+}
+
+impl Project {
+    /// Looks for a project file in the given directory and each of its
+    /// ancestors, returning the closest one.
+    pub fn discover(directory: &Path) -> Result<Option<Project>, ProjectReadError> {
+        for ancestor in directory.ancestors() {
+            let path = ancestor.join(PROJECT_FILE_NAME);
+
+            if path.is_file() {
+                return Project::read_from_file(&path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn read_from_file(path: &Path) -> Result<Project, ProjectReadError> {
+        let contents = fs::read_to_string(path).map_err(|error| ProjectReadError {
+            path: path.to_owned(),
+            kind: ProjectReadErrorKind::Io(error),
+        })?;
+
+        let mut project: Project = toml::from_str(&contents).map_err(|error| ProjectReadError {
+            path: path.to_owned(),
+            kind: ProjectReadErrorKind::InvalidToml(error),
+        })?;
+
+        if let Some(root) = path.parent() {
+            project.resolve_paths(root);
+        }
+
+        Ok(project)
+    }
+
+    /// Reads the JSON API dump, generating one with Roblox Studio if the
+    /// project doesn't name one.
+    pub fn load_dump(&self) -> Result<Dump, Error> {
+        let path = self.inputs.dump.as_deref();
+
+        Dump::read(path)
+            .map_err(|error| with_optional_path(Error::new(Stage::LoadDump, error), path))
+    }
+
+    /// The supplemental content directory, which has no default.
+    pub fn content_path(&self) -> Result<&Path, Error> {
+        required_input(
+            &self.inputs.content,
+            Stage::LoadContent,
+            "content",
+            "Inputs.Content",
+        )
+    }
+
+    pub fn load_content(&self) -> Result<SupplementalData, Error> {
+        let path = self.content_path()?;
+
+        SupplementalData::read_from_path(path)
+            .map_err(|error| Error::new(Stage::LoadContent, error).with_path(path))
+    }
+
+    /// Reads ReflectionMetadata, unless the project has turned it off.
+    pub fn load_reflection_metadata(&self) -> Result<Option<ReflectionMetadata>, Error> {
+        if !self.sources.reflection_metadata {
+            return Ok(None);
+        }
+
+        let path = self.inputs.metadata.as_deref();
+
+        ReflectionMetadata::read(path).map(Some).map_err(|error| {
+            with_optional_path(Error::new(Stage::LoadReflectionMetadata, error), path)
+        })
+    }
+
+    /// Reads DevHub pages if the project names a directory of them, whether
+    /// or not they're used as a content source.
+    pub fn load_devhub(&self) -> Result<Option<DevHubData>, Error> {
+        match &self.inputs.dev_hub {
+            Some(path) => DevHubData::read_from_dir(path)
+                .map(Some)
+                .map_err(|error| Error::new(Stage::LoadDevHub, error).with_path(path)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn load_history(&self) -> Result<Option<VersionHistory>, Error> {
+        match &self.inputs.history {
+            Some(path) => VersionHistory::read_from_dir(path)
+                .map(Some)
+                .map_err(|error| Error::new(Stage::LoadHistory, error).with_path(path)),
+            None => Ok(None),
+        }
+    }
+
+    /// The name the dump being documented goes by in its history: the dump's
+    /// file name, or `current` if it's generated by Roblox Studio.
+    fn current_version_name(&self) -> String {
+        self.inputs
+            .dump
+            .as_deref()
+            .and_then(Path::file_stem)
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "current".to_owned())
+    }
+
+    /// Reads the dump and merges every source and heuristic the project has
+    /// turned on into it, in the order described in [`merge`].
+    pub fn load_merged_dump(&self) -> Result<Dump, Error> {
+        let precedence = &self.precedence;
+        let mut dump = self.load_dump()?;
+
+        if let Some(metadata) = self.load_reflection_metadata()? {
+            merge::apply_reflection_metadata(&mut dump, &metadata, precedence);
+        }
+
+        if self.heuristics.camelcase_deprecated {
+            heuristics::superseded_members_probably_deprecated(&mut dump, precedence);
+        }
+
+        if self.sources.dev_hub {
+            required_input(
+                &self.inputs.dev_hub,
+                Stage::LoadDevHub,
+                "devhub",
+                "Inputs.DevHub",
+            )?;
+
+            if let Some(devhub) = self.load_devhub()? {
+                merge::apply_devhub(&mut dump, &devhub, precedence);
+            }
+        }
+
+        if self.sources.supplemental {
+            merge::apply_supplemental(&mut dump, &self.load_content()?, precedence);
+        }
+
+        if let Some(mut history) = self.load_history()? {
+            let current = self.current_version_name();
+
+            // The history directory may already hold the dump being documented.
+            if history.newest() != Some(current.as_str()) {
+                history.push(current, &dump);
+            }
+
+            merge::apply_history(&mut dump, &history);
+        }
+
+        Ok(dump)
+    }
+
+    fn resolve_paths(&mut self, root: &Path) {
+        let inputs = &mut self.inputs;
+        let outputs = &mut self.outputs;
+
+        let paths = vec![
+            &mut inputs.dump,
+            &mut inputs.metadata,
+            &mut inputs.content,
+            &mut inputs.history,
+            &mut inputs.dev_hub,
+            &mut outputs.miniwiki.output,
+            &mut outputs.megadump.output,
+            &mut outputs.coverage.output,
+            &mut outputs.disagreements.output,
+        ];
+
+        for path in paths.into_iter().flatten() {
+            *path = root.join(&path);
+        }
+    }
+}
+
+/// Finds an input that has no default, which must be given either on the
+/// command line or in the project file.
+fn required_input<'a>(
+    path: &'a Option<PathBuf>,
+    stage: Stage,
+    flag: &'static str,
+    key: &'static str,
+) -> Result<&'a Path, Error> {
+    path.as_deref()
+        .ok_or_else(|| Error::new(stage, ErrorKind::MissingInput { flag, key }))
+}
+
+fn with_optional_path(error: Error, path: Option<&Path>) -> Error {
+    match path {
+        Some(path) => error.with_path(path),
+        None => error,
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct ProjectInputs {
+    /// The JSON API dump. If unset, a fresh one is generated by Roblox Studio.
+    pub dump: Option<PathBuf>,
+
+    /// `ReflectionMetadata.xml`. If unset, it's read from the Roblox Studio
+    /// installation.
+    pub metadata: Option<PathBuf>,
+
+    /// The supplemental content directory or file.
+    pub content: Option<PathBuf>,
+
+    /// A directory of older JSON API dumps.
+    pub history: Option<PathBuf>,
+
+    /// A directory of DevHub class pages saved as JSON.
+    pub dev_hub: Option<PathBuf>,
+}
+
+/// Which content sources are read at all. A source that's turned off here
+/// doesn't need its input to exist.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct ProjectSources {
+    pub reflection_metadata: bool,
+    pub dev_hub: bool,
+    pub supplemental: bool,
+}
+
+impl Default for ProjectSources {
+    fn default() -> ProjectSources {
+        ProjectSources {
+            reflection_metadata: true,
+            dev_hub: false,
+            supplemental: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct ProjectHeuristics {
+    /// Mark superseded members as deprecated: members with a
+    /// `PreferredDescriptorName` tag, and camelCase members with a PascalCase
+    /// counterpart.
+    #[serde(rename = "CamelCaseDeprecated")]
+    pub camelcase_deprecated: bool,
+}
+
+impl Default for ProjectHeuristics {
+    fn default() -> ProjectHeuristics {
+        ProjectHeuristics {
+            camelcase_deprecated: true,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct ProjectOutputs {
+    pub miniwiki: MiniwikiOutput,
+    pub megadump: MegadumpOutput,
+    pub check: CheckOutput,
+    pub coverage: CoverageOutput,
+    pub disagreements: DisagreementsOutput,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct MiniwikiOutput {
+    pub output: Option<PathBuf>,
+    pub edit_url: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct MegadumpOutput {
+    pub output: Option<PathBuf>,
+    pub inheritance: bool,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct CheckOutput {
+    pub deny_warnings: bool,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct CoverageOutput {
+    pub output: Option<PathBuf>,
+    pub format: CoverageFormat,
+    pub exclude_deprecated: bool,
+    pub exclude_hidden: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageFormat {
+    #[default]
+    Table,
+    Json,
+    Html,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct DisagreementsOutput {
+    pub output: Option<PathBuf>,
+    pub format: DisagreementFormat,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DisagreementFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct ProjectReadError {
+    pub path: PathBuf,
+    pub kind: ProjectReadErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ProjectReadErrorKind {
+    Io(io::Error),
+    InvalidToml(toml::de::Error),
+}
+
+impl fmt::Display for ProjectReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}: ", self.path.display())?;
+
+        match &self.kind {
+            ProjectReadErrorKind::Io(error) => write!(formatter, "{}", error),
+            ProjectReadErrorKind::InvalidToml(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dump::ContentSource;
+
+    #[test]
+    fn discover_in_ancestors() {
+        let directory = tempfile::tempdir().unwrap();
+        let nested = directory.path().join("content").join("classes");
+        fs::create_dir_all(&nested).unwrap();
+
+        fs::write(
+            directory.path().join(PROJECT_FILE_NAME),
+            r#"
+                [Inputs]
+                Content = "content"
+
+                [Sources]
+                ReflectionMetadata = false
+
+                [Precedence]
+                Tags = ["ReflectionMetadata"]
+
+                [Outputs.Coverage]
+                Format = "html"
+            "#,
+        )
+        .unwrap();
+
+        let project = Project::discover(&nested).unwrap().unwrap();
+
+        assert_eq!(
+            project.inputs.content,
+            Some(directory.path().join("content"))
+        );
+        assert_eq!(project.inputs.dump, None);
+        assert!(!project.sources.reflection_metadata);
+        assert!(project.sources.supplemental);
+        assert!(project.heuristics.camelcase_deprecated);
+        assert_eq!(
+            project.precedence.tags,
+            vec![ContentSource::ReflectionMetadata]
+        );
+        match project.outputs.coverage.format {
+            CoverageFormat::Html => {}
+            other => panic!("Expected Html, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(PROJECT_FILE_NAME);
+        fs::write(&path, "[Inputs]\nContnet = \"content\"\n").unwrap();
+
+        let error = Project::read_from_file(&path).unwrap_err();
+        assert!(error.to_string().starts_with(&path.display().to_string()));
+        assert!(error.to_string().contains("Contnet"));
+    }
+}
@@ -3,10 +3,14 @@
 
 use std::{
     borrow::Cow,
+    cell::Cell,
     collections::HashMap,
-    fs,
-    io::{self, BufRead},
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
     path::Path,
+    rc::Rc,
+    str::FromStr,
 };
 
 use quick_xml::{
@@ -16,12 +20,17 @@ use quick_xml::{
 
 use lazy_static::lazy_static;
 use roblox_install::RobloxStudio;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::error::fmt_roblox_install_error;
 
 struct XmlQuery {
     pieces: Vec<(&'static str, Vec<(&'static str, &'static str)>)>,
 }
 
 impl XmlQuery {
+    /// Creates a query from tag names and the attributes each element must
+    /// have. A tag name of `*` matches any element.
     pub fn new(query: &[(&'static str, &[(&'static str, &'static str)])]) -> XmlQuery {
         let pieces = query
             .iter()
@@ -35,9 +44,9 @@ impl XmlQuery {
         &self,
         reader: &Reader<B>,
         element_stack: &[BytesStart<'static>],
-    ) -> bool {
+    ) -> Result<bool, quick_xml::Error> {
         if element_stack.len() != self.pieces.len() {
-            return false;
+            return Ok(false);
         }
 
         for (index, element) in element_stack.iter().enumerate() {
@@ -45,51 +54,188 @@ impl XmlQuery {
 
             let tag_name = reader.decode(element.name());
 
-            if tag_name != *expected_tag_name {
-                return false;
+            if *expected_tag_name != "*" && tag_name != *expected_tag_name {
+                return Ok(false);
             }
 
             if !expected_attributes.is_empty() {
-                let element_attributes = extract_attributes(reader, element.attributes());
+                let element_attributes = extract_attributes(reader, element.attributes())?;
 
                 for (key, expected_value) in expected_attributes {
                     match element_attributes.get(*key) {
                         Some(value) => {
                             if value != expected_value {
-                                return false;
+                                return Ok(false);
                             }
                         }
-                        None => return false,
+                        None => return Ok(false),
                     }
                 }
             }
         }
 
-        true
+        Ok(true)
     }
 }
 
 fn extract_attributes<B: BufRead>(
     reader: &Reader<B>,
     attributes: Attributes,
-) -> HashMap<String, String> {
+) -> Result<HashMap<String, String>, quick_xml::Error> {
     let mut output = HashMap::new();
 
     for attribute in attributes {
-        let attribute = attribute.unwrap();
-        let key = reader.decode(&attribute.key).to_string();
+        let attribute = attribute?;
+        let key = reader.decode(attribute.key).to_string();
         let value = reader.decode(&attribute.value).to_string();
 
         output.insert(key, value);
     }
 
-    output
+    Ok(output)
+}
+
+lazy_static! {
+    // Properties are usually strings, but newer files may use other kinds of
+    // values, which are read as text all the same.
+    static ref PROPERTY_QUERY: XmlQuery = XmlQuery::new(&[("Properties", &[]), ("*", &[])]);
+}
+
+/// The name of the property whose value is being read, if the reader is
+/// inside one of an item's properties.
+fn property_name<B: BufRead>(
+    reader: &Reader<B>,
+    relevant_stack: &[BytesStart<'static>],
+) -> Result<Option<String>, quick_xml::Error> {
+    if !PROPERTY_QUERY.matches(reader, relevant_stack)? {
+        return Ok(None);
+    }
+
+    match relevant_stack.last() {
+        Some(element) => Ok(extract_attributes(reader, element.attributes())?.remove("name")),
+        None => Ok(None),
+    }
+}
+
+/// Reads a property with a value like `true` or `5`, ignoring it if it's
+/// missing or malformed.
+fn parse_property<T: FromStr>(properties: &HashMap<String, String>, name: &str) -> Option<T> {
+    properties.get(name)?.trim().parse().ok()
+}
+
+/// Boolean properties are usually written `true` or `false`, but some are
+/// capitalized.
+fn parse_bool_property(properties: &HashMap<String, String>, name: &str) -> Option<bool> {
+    match properties.get(name)?.trim().to_ascii_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Describes where the reader is, like
+/// `roblox/Item[ReflectionMetadataClasses]/Item[ReflectionMetadataClass]`,
+/// using each element's `class` or `name` attribute if it has one.
+fn element_path<B: BufRead>(reader: &Reader<B>, element_stack: &[BytesStart<'static>]) -> String {
+    element_stack
+        .iter()
+        .map(|element| {
+            let tag_name = reader.decode(element.name());
+            let attributes = extract_attributes(reader, element.attributes()).unwrap_or_default();
+
+            match attributes.get("class").or_else(|| attributes.get("name")) {
+                Some(label) => format!("{}[{}]", tag_name, label),
+                None => tag_name.into_owned(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Wraps a reader to count the lines that have been consumed from it, since
+/// quick-xml only knows byte offsets.
+struct LineCounter<R> {
+    inner: R,
+    line: Rc<Cell<usize>>,
+}
+
+fn count_lines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&byte| byte == b'\n').count()
+}
+
+impl<R: BufRead> Read for LineCounter<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buffer)?;
+        self.line
+            .set(self.line.get() + count_lines(&buffer[..read]));
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for LineCounter<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        // The bytes being consumed are still at the front of the buffer.
+        if let Ok(buffer) = self.inner.fill_buf() {
+            let lines = count_lines(&buffer[..amount.min(buffer.len())]);
+            self.line.set(self.line.get() + lines);
+        }
+
+        self.inner.consume(amount);
+    }
 }
 
 #[derive(Debug)]
 pub enum ReflectionMetadataReadError {
     Io(io::Error),
     RobloxInstall(roblox_install::Error),
+
+    /// The file isn't well-formed XML.
+    Xml {
+        error: quick_xml::Error,
+
+        /// The byte offset the reader had reached when the error happened.
+        position: usize,
+
+        /// The 1-based line the reader had reached when the error happened.
+        line: usize,
+
+        /// The elements the reader was inside, as described by
+        /// `element_path`.
+        path: String,
+    },
+}
+
+impl fmt::Display for ReflectionMetadataReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReflectionMetadataReadError::Io(error) => write!(formatter, "{}", error),
+            ReflectionMetadataReadError::RobloxInstall(error) => {
+                fmt_roblox_install_error(error, formatter)
+            }
+            ReflectionMetadataReadError::Xml {
+                error,
+                position,
+                line,
+                path,
+            } => {
+                write!(
+                    formatter,
+                    "invalid XML on line {} (byte {})",
+                    line, position
+                )?;
+
+                if !path.is_empty() {
+                    write!(formatter, " in {}", path)?;
+                }
+
+                write!(formatter, ": {}", error)
+            }
+        }
+    }
 }
 
 impl From<io::Error> for ReflectionMetadataReadError {
@@ -107,11 +253,17 @@ impl From<roblox_install::Error> for ReflectionMetadataReadError {
 #[derive(Debug)]
 pub struct ReflectionMetadata {
     pub classes: HashMap<String, ReflectionMetadataClass>,
+    pub enums: HashMap<String, ReflectionMetadataEnum>,
 }
 
 impl ReflectionMetadata {
+    /// Reads the file at the given path, stdin if the path is `-`, or the one
+    /// from the Roblox Studio installation if there's no path.
     pub fn read(path: Option<&Path>) -> Result<ReflectionMetadata, ReflectionMetadataReadError> {
         let path = match path {
+            Some(p) if p == Path::new("-") => {
+                return ReflectionMetadata::read_from_reader(io::stdin().lock());
+            }
             Some(p) => Cow::Borrowed(p),
             None => Cow::Owned(
                 RobloxStudio::locate()?
@@ -120,19 +272,48 @@ impl ReflectionMetadata {
             ),
         };
 
-        Ok(ReflectionMetadata::read_from_file(path.as_ref())?)
+        ReflectionMetadata::read_from_file(path.as_ref())
     }
 
-    pub fn read_from_file(path: &Path) -> io::Result<ReflectionMetadata> {
-        let contents = fs::read_to_string(path)?;
+    pub fn read_from_file(path: &Path) -> Result<ReflectionMetadata, ReflectionMetadataReadError> {
+        let file = File::open(path)?;
 
-        let mut classes = HashMap::new();
+        ReflectionMetadata::read_from_reader(BufReader::new(file))
+    }
 
-        let mut reader = Reader::from_str(&contents);
+    /// Parses ReflectionMetadata as it's read, without loading all of it into
+    /// memory first, so it can come from an archive or stdin as well as a file.
+    pub fn read_from_reader<R: BufRead>(
+        source: R,
+    ) -> Result<ReflectionMetadata, ReflectionMetadataReadError> {
+        let line = Rc::new(Cell::new(1));
+        let mut reader = Reader::from_reader(LineCounter {
+            inner: source,
+            line: Rc::clone(&line),
+        });
         reader.trim_text(true);
 
+        let mut element_stack = Vec::new();
+
+        ReflectionMetadata::decode(&mut reader, &mut element_stack).map_err(|error| match error {
+            quick_xml::Error::Io(error) => ReflectionMetadataReadError::Io(error),
+            error => ReflectionMetadataReadError::Xml {
+                error,
+                position: reader.buffer_position(),
+                line: line.get(),
+                path: element_path(&reader, &element_stack),
+            },
+        })
+    }
+
+    fn decode<B: BufRead>(
+        reader: &mut Reader<B>,
+        element_stack: &mut Vec<BytesStart<'static>>,
+    ) -> Result<ReflectionMetadata, quick_xml::Error> {
+        let mut classes = HashMap::new();
+        let mut enums = HashMap::new();
+
         let mut xml_buffer = Vec::new();
-        let mut element_stack: Vec<BytesStart<'static>> = Vec::new();
 
         lazy_static! {
             static ref CLASS_QUERY: XmlQuery = XmlQuery::new(&[
@@ -140,6 +321,11 @@ impl ReflectionMetadata {
                 ("Item", &[]),
                 ("Item", &[("class", "ReflectionMetadataClass")]),
             ]);
+            static ref ENUM_QUERY: XmlQuery = XmlQuery::new(&[
+                ("roblox", &[]),
+                ("Item", &[]),
+                ("Item", &[("class", "ReflectionMetadataEnum")]),
+            ]);
         }
 
         loop {
@@ -147,57 +333,106 @@ impl ReflectionMetadata {
                 Ok(Event::Start(element)) => {
                     element_stack.push(element.into_owned());
 
-                    if CLASS_QUERY.matches(&reader, &element_stack) {
-                        let class =
-                            ReflectionMetadataClass::decode(&mut reader, &mut element_stack);
+                    if CLASS_QUERY.matches(reader, element_stack)? {
+                        let class = ReflectionMetadataClass::decode(reader, element_stack)?;
                         classes.insert(class.name.clone(), class);
+                    } else if ENUM_QUERY.matches(reader, element_stack)? {
+                        let enum_metadata = ReflectionMetadataEnum::decode(reader, element_stack)?;
+                        enums.insert(enum_metadata.name.clone(), enum_metadata);
                     }
                 }
                 Ok(Event::End(_)) => {
                     element_stack.pop();
                 }
-                Ok(Event::Eof) => break,
-                Err(_) => panic!("Error parsing XML!"),
+                Ok(Event::Eof) => {
+                    if !element_stack.is_empty() {
+                        return Err(unexpected_eof());
+                    }
+
+                    break;
+                }
+                Err(error) => return Err(error),
                 _ => {}
             }
 
             xml_buffer.clear();
         }
 
-        Ok(ReflectionMetadata { classes })
+        Ok(ReflectionMetadata { classes, enums })
     }
 }
 
+/// The error for a file that ends before all of its elements are closed,
+/// which quick-xml doesn't report on its own.
+fn unexpected_eof() -> quick_xml::Error {
+    quick_xml::Error::UnexpectedEof("element".to_owned())
+}
+
 #[derive(Debug)]
 pub struct ReflectionMetadataClass {
     pub name: String,
     pub summary: String,
+    pub attributes: ReflectionMetadataClassAttributes,
     pub members: HashMap<String, ReflectionMetadataMember>,
 }
 
+/// The properties `ReflectionMetadata.xml` gives a class besides its name and
+/// summary. Each is `None` if the file doesn't set it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReflectionMetadataClassAttributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browsable: Option<bool>,
+
+    /// The group the class is listed under in Studio's Insert Object window.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_category: Option<String>,
+
+    /// Where instances of the class sort in Studio's Explorer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_order: Option<i32>,
+
+    /// The index of the class's icon in Studio's Explorer icon sheet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_image_index: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insertable: Option<bool>,
+
+    /// The class new instances are parented to when inserted in Studio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_parent: Option<String>,
+}
+
+impl ReflectionMetadataClassAttributes {
+    fn from_properties(properties: &HashMap<String, String>) -> ReflectionMetadataClassAttributes {
+        ReflectionMetadataClassAttributes {
+            deprecated: parse_bool_property(properties, "deprecated"),
+            browsable: parse_bool_property(properties, "Browsable"),
+            class_category: properties.get("ClassCategory").cloned(),
+            explorer_order: parse_property(properties, "ExplorerOrder"),
+            explorer_image_index: parse_property(properties, "ExplorerImageIndex"),
+            insertable: parse_bool_property(properties, "Insertable"),
+            preferred_parent: properties.get("PreferredParent").cloned(),
+        }
+    }
+}
+
 impl ReflectionMetadataClass {
     fn decode<B: BufRead>(
         reader: &mut Reader<B>,
         element_stack: &mut Vec<BytesStart<'static>>,
-    ) -> ReflectionMetadataClass {
-        let mut name = String::new();
-        let mut summary = String::new();
+    ) -> Result<ReflectionMetadataClass, quick_xml::Error> {
+        let mut properties = HashMap::new();
         let mut members = HashMap::new();
 
         let start_stack_len = element_stack.len();
         let mut xml_buffer = Vec::new();
 
         lazy_static! {
-            static ref NAME_QUERY: XmlQuery = XmlQuery::new(&[
-                ("Properties", &[]),
-                ("string", &[("name", "Name")]),
-            ]);
-
-            static ref SUMMARY_QUERY: XmlQuery = XmlQuery::new(&[
-                ("Properties", &[]),
-                ("string", &[("name", "summary")]),
-            ]);
-
             static ref MEMBER_QUERY: XmlQuery = XmlQuery::new(&[
                 ("Item", &[]), // class is "ReflectionMetadataFunctions" or similar; we don't care.
                 ("Item", &[("class", "ReflectionMetadataMember")]),
@@ -209,8 +444,8 @@ impl ReflectionMetadataClass {
                 Ok(Event::Start(element)) => {
                     element_stack.push(element.into_owned());
 
-                    if MEMBER_QUERY.matches(&reader, &element_stack[start_stack_len..]) {
-                        let member = ReflectionMetadataMember::decode(reader, element_stack);
+                    if MEMBER_QUERY.matches(reader, &element_stack[start_stack_len..])? {
+                        let member = ReflectionMetadataMember::decode(reader, element_stack)?;
 
                         members.insert(member.name.clone(), member);
                     }
@@ -225,25 +460,24 @@ impl ReflectionMetadataClass {
                 Ok(Event::Text(text)) => {
                     let relevant_stack = &element_stack[start_stack_len..];
 
-                    if NAME_QUERY.matches(&reader, relevant_stack) {
-                        name = text.unescape_and_decode(reader).unwrap();
-                    } else if SUMMARY_QUERY.matches(&reader, relevant_stack) {
-                        summary = text.unescape_and_decode(reader).unwrap();
+                    if let Some(property) = property_name(reader, relevant_stack)? {
+                        properties.insert(property, text.unescape_and_decode(reader)?);
                     }
                 }
-                Ok(Event::Eof) => break,
-                Err(_) => panic!("Error parsing ReflectionMetadataClass!"),
+                Ok(Event::Eof) => return Err(unexpected_eof()),
+                Err(error) => return Err(error),
                 _ => {}
             }
 
             xml_buffer.clear();
         }
 
-        ReflectionMetadataClass {
-            name,
-            summary,
+        Ok(ReflectionMetadataClass {
+            name: properties.remove("Name").unwrap_or_default(),
+            summary: properties.remove("summary").unwrap_or_default(),
+            attributes: ReflectionMetadataClassAttributes::from_properties(&properties),
             members,
-        }
+        })
     }
 }
 
@@ -251,30 +485,167 @@ impl ReflectionMetadataClass {
 pub struct ReflectionMetadataMember {
     pub name: String,
     pub summary: String,
+    pub attributes: ReflectionMetadataMemberAttributes,
+}
+
+/// The properties `ReflectionMetadata.xml` gives a member or enum item besides
+/// its name and summary. Each is `None` if the file doesn't set it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReflectionMetadataMemberAttributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browsable: Option<bool>,
+
+    /// Whether the member is meant for `Server` or `Client` scripts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_context: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_backend: Option<bool>,
+
+    /// The lower bound of a property's slider in Studio's Properties window.
+    #[serde(rename = "UIMinimum", skip_serializing_if = "Option::is_none")]
+    pub ui_minimum: Option<f64>,
+
+    /// The upper bound of a property's slider in Studio's Properties window.
+    #[serde(rename = "UIMaximum", skip_serializing_if = "Option::is_none")]
+    pub ui_maximum: Option<f64>,
+
+    /// How many steps a property's slider in Studio's Properties window has.
+    #[serde(rename = "UINumTicks", skip_serializing_if = "Option::is_none")]
+    pub ui_num_ticks: Option<u32>,
+
+    /// The range a property's value is limited to, like `0:1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+}
+
+impl ReflectionMetadataMemberAttributes {
+    fn from_properties(properties: &HashMap<String, String>) -> ReflectionMetadataMemberAttributes {
+        ReflectionMetadataMemberAttributes {
+            deprecated: parse_bool_property(properties, "deprecated"),
+            browsable: parse_bool_property(properties, "Browsable"),
+            script_context: properties.get("ScriptContext").cloned(),
+            is_backend: parse_bool_property(properties, "IsBackend"),
+            ui_minimum: parse_property(properties, "UIMinimum"),
+            ui_maximum: parse_property(properties, "UIMaximum"),
+            ui_num_ticks: parse_property(properties, "UINumTicks"),
+            constraint: properties.get("Constraint").cloned(),
+        }
+    }
 }
 
 impl ReflectionMetadataMember {
     fn decode<B: BufRead>(
         reader: &mut Reader<B>,
         element_stack: &mut Vec<BytesStart<'static>>,
-    ) -> ReflectionMetadataMember {
-        let mut name = String::new();
-        let mut summary = String::new();
+    ) -> Result<ReflectionMetadataMember, quick_xml::Error> {
+        let mut properties = HashMap::new();
+
+        let start_stack_len = element_stack.len();
+        let mut xml_buffer = Vec::new();
+
+        loop {
+            match reader.read_event(&mut xml_buffer) {
+                Ok(Event::Start(element)) => {
+                    element_stack.push(element.into_owned());
+                }
+                Ok(Event::End(_)) => {
+                    element_stack.pop();
+
+                    if element_stack.len() < start_stack_len {
+                        break;
+                    }
+                }
+                Ok(Event::Text(text)) => {
+                    let relevant_stack = &element_stack[start_stack_len..];
+
+                    if let Some(property) = property_name(reader, relevant_stack)? {
+                        properties.insert(property, text.unescape_and_decode(reader)?);
+                    }
+                }
+                Ok(Event::Eof) => return Err(unexpected_eof()),
+                Err(error) => return Err(error),
+                _ => {}
+            }
+
+            xml_buffer.clear();
+        }
+
+        let summary = properties
+            .remove("summary")
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim_start)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(ReflectionMetadataMember {
+            name: properties.remove("Name").unwrap_or_default(),
+            summary,
+            attributes: ReflectionMetadataMemberAttributes::from_properties(&properties),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ReflectionMetadataEnum {
+    pub name: String,
+    pub summary: String,
+    pub attributes: ReflectionMetadataEnumAttributes,
+    pub items: HashMap<String, ReflectionMetadataEnumItem>,
+}
+
+/// The properties `ReflectionMetadata.xml` gives an enum besides its name and
+/// summary. Each is `None` if the file doesn't set it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReflectionMetadataEnumAttributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browsable: Option<bool>,
+}
+
+impl ReflectionMetadataEnumAttributes {
+    fn from_properties(properties: &HashMap<String, String>) -> ReflectionMetadataEnumAttributes {
+        ReflectionMetadataEnumAttributes {
+            deprecated: parse_bool_property(properties, "deprecated"),
+            browsable: parse_bool_property(properties, "Browsable"),
+        }
+    }
+}
+
+impl ReflectionMetadataEnum {
+    fn decode<B: BufRead>(
+        reader: &mut Reader<B>,
+        element_stack: &mut Vec<BytesStart<'static>>,
+    ) -> Result<ReflectionMetadataEnum, quick_xml::Error> {
+        let mut properties = HashMap::new();
+        let mut items = HashMap::new();
 
         let start_stack_len = element_stack.len();
         let mut xml_buffer = Vec::new();
 
         lazy_static! {
-            static ref NAME_QUERY: XmlQuery =
-                XmlQuery::new(&[("Properties", &[]), ("string", &[("name", "Name")]),]);
-            static ref SUMMARY_QUERY: XmlQuery =
-                XmlQuery::new(&[("Properties", &[]), ("string", &[("name", "summary")]),]);
+            static ref ITEM_QUERY: XmlQuery =
+                XmlQuery::new(&[("Item", &[("class", "ReflectionMetadataEnumItem")])]);
         }
 
         loop {
             match reader.read_event(&mut xml_buffer) {
                 Ok(Event::Start(element)) => {
                     element_stack.push(element.into_owned());
+
+                    if ITEM_QUERY.matches(reader, &element_stack[start_stack_len..])? {
+                        let item = ReflectionMetadataEnumItem::decode(reader, element_stack)?;
+
+                        items.insert(item.name.clone(), item);
+                    }
                 }
                 Ok(Event::End(_)) => {
                     element_stack.pop();
@@ -286,26 +657,204 @@ impl ReflectionMetadataMember {
                 Ok(Event::Text(text)) => {
                     let relevant_stack = &element_stack[start_stack_len..];
 
-                    if NAME_QUERY.matches(&reader, relevant_stack) {
-                        name = text.unescape_and_decode(reader).unwrap();
-                    } else if SUMMARY_QUERY.matches(&reader, relevant_stack) {
-                        summary = text
-                            .unescape_and_decode(reader)
-                            .unwrap()
-                            .lines()
-                            .map(str::trim_start)
-                            .collect::<Vec<_>>()
-                            .join("\n\n");
+                    if let Some(property) = property_name(reader, relevant_stack)? {
+                        properties.insert(property, text.unescape_and_decode(reader)?);
                     }
                 }
-                Ok(Event::Eof) => break,
-                Err(_) => panic!("Error parsing ReflectionMetadataClass!"),
+                Ok(Event::Eof) => return Err(unexpected_eof()),
+                Err(error) => return Err(error),
                 _ => {}
             }
 
             xml_buffer.clear();
         }
 
-        ReflectionMetadataMember { name, summary }
+        Ok(ReflectionMetadataEnum {
+            name: properties.remove("Name").unwrap_or_default(),
+            summary: properties.remove("summary").unwrap_or_default(),
+            attributes: ReflectionMetadataEnumAttributes::from_properties(&properties),
+            items,
+        })
+    }
+}
+
+/// Enum items are described with the same properties as class members.
+pub type ReflectionMetadataEnumItem = ReflectionMetadataMember;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn attributes() {
+        let source = r#"<roblox version="4">
+<Item class="ReflectionMetadataClasses">
+  <Item class="ReflectionMetadataClass">
+    <Properties>
+      <string name="Name">Part</string>
+      <string name="summary">A part.</string>
+      <string name="ClassCategory">Parts</string>
+      <string name="ExplorerOrder">3</string>
+      <string name="ExplorerImageIndex">1</string>
+      <string name="PreferredParent">Workspace</string>
+      <string name="Insertable">true</string>
+    </Properties>
+    <Item class="ReflectionMetadataProperties">
+      <Item class="ReflectionMetadataMember">
+        <Properties>
+          <string name="Name">Transparency</string>
+          <string name="UIMinimum">0</string>
+          <string name="UIMaximum">1</string>
+          <string name="UINumTicks">10</string>
+          <string name="Constraint">0:1</string>
+        </Properties>
+      </Item>
+      <Item class="ReflectionMetadataMember">
+        <Properties>
+          <string name="Name">brickColor</string>
+          <string name="deprecated">true</string>
+          <string name="Browsable">False</string>
+          <string name="ScriptContext">Server</string>
+          <string name="IsBackend">nonsense</string>
+        </Properties>
+      </Item>
+    </Item>
+  </Item>
+</Item>
+</roblox>"#;
+
+        let metadata = ReflectionMetadata::read_from_reader(source.as_bytes()).unwrap();
+        let part = &metadata.classes["Part"];
+
+        assert_eq!(part.summary, "A part.");
+        assert_eq!(
+            part.attributes,
+            ReflectionMetadataClassAttributes {
+                class_category: Some("Parts".to_owned()),
+                explorer_order: Some(3),
+                explorer_image_index: Some(1),
+                insertable: Some(true),
+                preferred_parent: Some("Workspace".to_owned()),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            part.members["Transparency"].attributes,
+            ReflectionMetadataMemberAttributes {
+                ui_minimum: Some(0.0),
+                ui_maximum: Some(1.0),
+                ui_num_ticks: Some(10),
+                constraint: Some("0:1".to_owned()),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            part.members["brickColor"].attributes,
+            ReflectionMetadataMemberAttributes {
+                deprecated: Some(true),
+                browsable: Some(false),
+                script_context: Some("Server".to_owned()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn enums() {
+        let source = r#"<roblox version="4">
+<Item class="ReflectionMetadataEnums">
+  <Item class="ReflectionMetadataEnum">
+    <Properties>
+      <string name="Name">Material</string>
+      <string name="summary">What a part is made of.</string>
+      <string name="deprecated">false</string>
+      <bool name="Browsable">false</bool>
+    </Properties>
+    <Item class="ReflectionMetadataEnumItem">
+      <Properties>
+        <string name="Name">Plastic</string>
+        <string name="summary">The default material.</string>
+      </Properties>
+    </Item>
+    <Item class="ReflectionMetadataEnumItem">
+      <Properties>
+        <string name="Name">Wood</string>
+      </Properties>
+    </Item>
+  </Item>
+</Item>
+</roblox>"#;
+
+        let metadata = ReflectionMetadata::read_from_reader(source.as_bytes()).unwrap();
+        assert!(metadata.classes.is_empty());
+
+        let material = &metadata.enums["Material"];
+        assert_eq!(material.summary, "What a part is made of.");
+        assert_eq!(
+            material.attributes,
+            ReflectionMetadataEnumAttributes {
+                deprecated: Some(false),
+                browsable: Some(false),
+            }
+        );
+        assert_eq!(material.items.len(), 2);
+        assert_eq!(material.items["Plastic"].summary, "The default material.");
+        assert_eq!(material.items["Wood"].summary, "");
+    }
+
+    #[test]
+    fn unknown_elements() {
+        let source = r#"<roblox version="4">
+<Meta name="ExplicitAutoJoints">true</Meta>
+<Item class="ReflectionMetadataClasses">
+  <Item class="ReflectionMetadataClass">
+    <Properties>
+      <string name="Name">Part</string>
+      <bool name="deprecated">true</bool>
+      <Unknown name="Browsable"><Nested/>false</Unknown>
+    </Properties>
+    <Item class="ReflectionMetadataSomethingNew">
+      <Item class="ReflectionMetadataMember">
+        <Properties><string name="Name">Size</string></Properties>
+      </Item>
+    </Item>
+  </Item>
+</Item>
+<Item class="ReflectionMetadataSomethingElse"><Properties/></Item>
+</roblox>"#;
+
+        let metadata = ReflectionMetadata::read_from_reader(source.as_bytes()).unwrap();
+        let part = &metadata.classes["Part"];
+
+        assert_eq!(part.attributes.deprecated, Some(true));
+        assert_eq!(part.attributes.browsable, Some(false));
+        assert!(part.members.contains_key("Size"));
+    }
+
+    #[test]
+    fn xml_errors() {
+        let source = "<roblox version=\"4\">\n<Item class=\"ReflectionMetadataClasses\">\n  <Item class=\"ReflectionMetadataClass\">\n    <Properties>\n      <string name=\"Name\">Part</strin>\n";
+
+        match ReflectionMetadata::read_from_reader(source.as_bytes()) {
+            Err(ReflectionMetadataReadError::Xml { line, path, .. }) => {
+                assert_eq!(line, 5);
+                assert_eq!(
+                    path,
+                    "roblox/Item[ReflectionMetadataClasses]/Item[ReflectionMetadataClass]/Properties/string[Name]"
+                );
+            }
+            other => panic!("Expected an XML error, got {:?}", other),
+        }
+
+        let truncated = "<roblox version=\"4\">\n<Item class=\"ReflectionMetadataClasses\">\n";
+
+        match ReflectionMetadata::read_from_reader(truncated.as_bytes()) {
+            Err(ReflectionMetadataReadError::Xml { path, .. }) => {
+                assert_eq!(path, "roblox/Item[ReflectionMetadataClasses]");
+            }
+            other => panic!("Expected an XML error, got {:?}", other),
+        }
     }
 }
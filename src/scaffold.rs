@@ -0,0 +1,204 @@
+//! Generates stub content for classes and members that don't have
+//! supplemental descriptions yet, giving writers a starting point.
+
+use std::fmt::{self, Write};
+
+use crate::{
+    diff::{render_parameters, render_return_type},
+    dump::{DumpClass, DumpClassMember},
+    reflection_metadata::ReflectionMetadataClass,
+    supplement::SupplementalData,
+};
+
+/// Writes a stub item description for the class and each of its members that
+/// the content doesn't already describe, returning how many were written.
+///
+/// Stubs are marked `Stub = true` so that they aren't used as descriptions
+/// until a writer fills them in. They're pre-filled with the ReflectionMetadata
+/// summary, if there is one, and members are annotated with their signature in
+/// a comment in the metadata block.
+pub fn emit_class_stubs(
+    class: &DumpClass,
+    metadata: Option<&ReflectionMetadataClass>,
+    content: &SupplementalData,
+    output: &mut String,
+) -> Result<usize, fmt::Error> {
+    let mut count = 0;
+
+    if !content.item_descriptions.contains_key(&class.name) {
+        let summary = metadata.map(|metadata| metadata.summary.as_str());
+        emit_stub(&class.name, None, summary, output)?;
+        count += 1;
+    }
+
+    for member in &class.members {
+        if let DumpClassMember::Unknown(_) = member {
+            continue;
+        }
+
+        let target = format!("{}.{}", class.name, member.get_name());
+
+        if content.item_descriptions.contains_key(&target) {
+            continue;
+        }
+
+        let summary = metadata
+            .and_then(|metadata| metadata.members.get(member.get_name()))
+            .map(|metadata_member| metadata_member.summary.as_str());
+
+        let signature = member_signature(&class.name, member);
+        emit_stub(&target, Some(&signature), summary, output)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+fn emit_stub(
+    target: &str,
+    signature: Option<&str>,
+    summary: Option<&str>,
+    output: &mut String,
+) -> fmt::Result {
+    if !output.is_empty() {
+        writeln!(output)?;
+    }
+
+    writeln!(output, "+++")?;
+    writeln!(
+        output,
+        "Target = {}",
+        toml::Value::String(target.to_owned())
+    )?;
+    writeln!(output, "Stub = true")?;
+
+    if let Some(signature) = signature {
+        writeln!(output, "# {}", signature)?;
+    }
+
+    writeln!(output, "+++")?;
+
+    match summary {
+        Some(summary) if !summary.is_empty() => {
+            writeln!(output)?;
+            writeln!(output, "{}", summary)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Renders a member's signature in a Lua-like style, like
+/// `function Instance:FindFirstChild(name: string, recursive: bool): Instance`.
+fn member_signature(class_name: &str, member: &DumpClassMember) -> String {
+    match member {
+        DumpClassMember::Property(property) => format!(
+            "property {}.{}: {}",
+            class_name,
+            property.name,
+            property.value_type.simple_name()
+        ),
+        DumpClassMember::Function(function) => format!(
+            "function {}:{}{}: {}",
+            class_name,
+            function.name,
+            render_parameters(&function.parameters),
+            render_return_type(&function.return_type)
+        ),
+        DumpClassMember::Event(event) => format!(
+            "event {}.{}{}",
+            class_name,
+            event.name,
+            render_parameters(&event.parameters)
+        ),
+        DumpClassMember::Callback(callback) => format!(
+            "callback {}.{}{}: {}",
+            class_name,
+            callback.name,
+            render_parameters(&callback.parameters),
+            render_return_type(&callback.return_type)
+        ),
+        DumpClassMember::Unknown(unknown) => {
+            format!("{} {}.{}", unknown.member_type, class_name, unknown.name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::{collections::HashMap, fs};
+
+    use crate::{dump::Dump, reflection_metadata::ReflectionMetadataMember};
+
+    #[test]
+    fn skip_described_targets() {
+        let dump: Dump = serde_json::from_str(
+            r#"{
+                "Version": 1,
+                "Classes": [{
+                    "Name": "Part",
+                    "Members": [
+                        {
+                            "MemberType": "Function",
+                            "Name": "Resize",
+                            "Parameters": [{ "Name": "normalId", "Type": { "Name": "NormalId", "Category": "Enum" } }],
+                            "ReturnType": { "Name": "bool", "Category": "Primitive" },
+                            "Security": "None"
+                        },
+                        {
+                            "MemberType": "Event",
+                            "Name": "Touched",
+                            "Parameters": [],
+                            "Security": "None"
+                        }
+                    ]
+                }],
+                "Enums": []
+            }"#,
+        )
+        .unwrap();
+
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(
+            directory.path().join("Part.md"),
+            "+++\nTarget = \"Part.Touched\"\n+++\nFires on touch.\n",
+        )
+        .unwrap();
+        let content = SupplementalData::read_from_path(directory.path()).unwrap();
+
+        let mut members = HashMap::new();
+        members.insert(
+            "Resize".to_owned(),
+            ReflectionMetadataMember {
+                name: "Resize".to_owned(),
+                summary: "Grows the part.".to_owned(),
+                attributes: Default::default(),
+            },
+        );
+        let metadata = ReflectionMetadataClass {
+            name: "Part".to_owned(),
+            summary: String::new(),
+            attributes: Default::default(),
+            members,
+        };
+
+        let mut output = String::new();
+        let count =
+            emit_class_stubs(&dump.classes[0], Some(&metadata), &content, &mut output).unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            output,
+            "+++\nTarget = \"Part\"\nStub = true\n+++\n\
+             \n\
+             +++\nTarget = \"Part.Resize\"\nStub = true\n\
+             # function Part:Resize(normalId: Enum.NormalId): bool\n+++\n\n\
+             Grows the part.\n"
+        );
+
+        let stubs = SupplementalData::read_from_str(&output).unwrap();
+        assert!(stubs.item_descriptions["Part.Resize"].metadata.stub);
+        assert!(stubs.find_item("Part.Resize").is_none());
+    }
+}
@@ -8,38 +8,136 @@
 //!
 //! ```md
 //! +++
-//! target = "Instance"
+//! Target = "Instance"
 //! +++
 //!
 //! The base class for all Roblox instances.
 //!
 //! +++
-//! target = "Instance.Name"
+//! Target = "Instance.Name"
 //! +++
 //!
 //! A handy name to refer to the `Instance` with.
 //! ```
+//!
+//! Enums and their items are targeted with an `Enum.` prefix, like
+//! `Enum.Material` or `Enum.Material.Plastic`.
+//!
+//! Parameters of functions, events, and callbacks can be documented with a
+//! `Parameters` table in the metadata block:
+//!
+//! ```md
+//! +++
+//! Target = "Instance.FindFirstChild"
+//!
+//! [Parameters.recursive]
+//! Description = "Whether to search descendants instead of only children."
+//! Optional = true
+//! Default = "false"
+//! +++
+//! ```
+//!
+//! By default, an item's prose replaces descriptions from lower-priority
+//! sources, like ReflectionMetadata. Declare `Mode = "append"` or
+//! `Mode = "prepend"` to keep that description and add to it instead.
+//!
+//! Each target may only be described once across the whole content tree. To
+//! add to another item in the content tree, like one in another file, declare
+//! how the two items should be merged:
+//!
+//! ```md
+//! +++
+//! Target = "Instance.Name"
+//! Merge = "append"
+//! +++
+//!
+//! Names don't have to be unique.
+//! ```
+//!
+//! An item can't declare both `Mode` and `Merge`, since only the main item's
+//! `Mode` decides how the combined prose meets lower-priority sources.
+//!
+//! Items written by `scaffold` are marked `Stub = true` until a writer fills
+//! them in and removes the marker. Stubs are never used as descriptions.
 
-use std::{collections::HashMap, fs, io, path::Path};
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
 
 use serde_derive::Deserialize;
 
+use crate::dump::DescriptionOrigin;
+
 // This is inspired by Hugo's TOML front-matter indicator
 // --- is used for YAML, and +++ is used to disambiguate for TOML.
 const METADATA_FENCE: &str = "+++";
 
 #[derive(Debug)]
 pub struct SupplementalData {
+    /// The file or directory the content was read from.
+    pub path: PathBuf,
+
     pub item_descriptions: HashMap<String, ItemDescription>,
 }
 
 impl SupplementalData {
+    /// Reads a single content file, or every file in a content directory and
+    /// its subdirectories.
     pub fn read_from_path(path: &Path) -> Result<SupplementalData, ReadError> {
-        let mut item_descriptions = HashMap::new();
+        let mut items = Vec::new();
+
+        read_item_descriptions_from_path(path, &mut items)?;
+
+        Ok(SupplementalData {
+            path: path.to_path_buf(),
+            item_descriptions: combine_item_descriptions(items)?,
+        })
+    }
+
+    /// Parses content that wasn't read from disk, like a single document.
+    /// Items parsed this way have no origin.
+    pub fn read_from_str(source: &str) -> Result<SupplementalData, ReadError> {
+        let items = parse_item_descriptions(source, None)?;
 
-        read_item_descriptions_from_path(path, &mut item_descriptions)?;
+        Ok(SupplementalData {
+            path: PathBuf::new(),
+            item_descriptions: combine_item_descriptions(items)?,
+        })
+    }
 
-        Ok(SupplementalData { item_descriptions })
+    /// Finds the item describing a target, leaving out stubs that haven't been
+    /// written yet.
+    pub fn find_item(&self, target: &str) -> Option<&ItemDescription> {
+        self.item_descriptions
+            .get(target)
+            .filter(|item| !item.metadata.stub)
+    }
+
+    /// Describes where an item came from relative to the content path, for
+    /// recording in the megadump.
+    pub fn origin(&self, description: &ItemDescription) -> Option<DescriptionOrigin> {
+        let path = description.location.path.as_ref()?;
+
+        let relative_path = if path == &self.path {
+            // The content path was a single file.
+            Path::new(path.file_name()?)
+        } else {
+            path.strip_prefix(&self.path).ok()?
+        };
+
+        let components: Vec<_> = relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect();
+
+        Some(DescriptionOrigin {
+            path: components.join("/"),
+            start_line: description.location.line,
+            end_line: description.location.end_line,
+        })
     }
 }
 
@@ -47,6 +145,32 @@ impl SupplementalData {
 pub struct ItemDescription {
     pub metadata: Metadata,
     pub prose: String,
+
+    /// Where the item's metadata block starts.
+    pub location: SourceLocation,
+}
+
+/// A position in the content tree.
+#[derive(Debug, Clone)]
+pub struct SourceLocation {
+    /// The file the item came from, if the content was read from disk.
+    pub path: Option<PathBuf>,
+
+    /// The 1-based line the item starts on.
+    pub line: usize,
+
+    /// The 1-based line the item's prose ends on, or its metadata block ends
+    /// on if it has no prose.
+    pub end_line: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(formatter, "{}:{}", path.display(), self.line),
+            None => write!(formatter, "<input>:{}", self.line),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -54,12 +178,120 @@ pub struct ItemDescription {
 pub struct Metadata {
     pub target: String,
     pub return_types: Option<Vec<String>>,
+
+    /// Documentation for the parameters of functions, events, and callbacks,
+    /// keyed by parameter name.
+    #[serde(default)]
+    pub parameters: HashMap<String, ParameterMetadata>,
+
+    /// Declares that this item adds to another item in the content tree with
+    /// the same target instead of conflicting with it.
+    pub merge: Option<MergeMode>,
+
+    /// How this item's prose is combined with a description of the same
+    /// target from a lower-priority source, like ReflectionMetadata. Items
+    /// that don't declare it replace the other description.
+    pub mode: Option<DescriptionMode>,
+
+    /// Marks an item written by `scaffold` that hasn't been filled in yet.
+    /// Stubs are never used as descriptions, and `check` warns about them.
+    #[serde(default)]
+    pub stub: bool,
+}
+
+/// How an item's prose is combined with a description from a lower-priority
+/// source, like ReflectionMetadata.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionMode {
+    /// The item's prose goes after the other description.
+    Append,
+
+    /// The item's prose goes before the other description.
+    Prepend,
+
+    /// The item's prose is used instead of the other description.
+    #[default]
+    Replace,
+}
+
+impl DescriptionMode {
+    /// Combines an item's prose with the description it's taking the place
+    /// of, if there is one.
+    pub fn combine(self, existing: Option<&str>, prose: &str) -> String {
+        match (self, existing) {
+            (DescriptionMode::Append, Some(existing)) => format!("{}\n\n{}", existing, prose),
+            (DescriptionMode::Prepend, Some(existing)) => format!("{}\n\n{}", prose, existing),
+            _ => prose.to_string(),
+        }
+    }
+}
+
+/// How an item with a `Merge` key is combined with the other descriptions of
+/// its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMode {
+    /// This item's prose goes after the main description's prose.
+    Append,
+
+    /// This item's prose goes before the main description's prose.
+    Prepend,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ParameterMetadata {
+    pub description: Option<String>,
+
+    /// Replaces the parameter's type from the dump, using the same simple
+    /// names as `ReturnTypes`. Names that aren't a class, an enum, or a type
+    /// the dump uses are ignored.
+    #[serde(rename = "Type")]
+    pub kind: Option<String>,
+
+    pub optional: Option<bool>,
+    pub default: Option<String>,
 }
 
 #[derive(Debug)]
 pub enum ReadError {
     IoError(io::Error),
     ParseError(ParseError),
+
+    /// Two items describe the same target and neither declares `Merge`.
+    DuplicateTarget {
+        target: String,
+        first: SourceLocation,
+        second: SourceLocation,
+    },
+
+    /// The content path is neither a file nor a directory, like a socket.
+    UnsupportedPath(PathBuf),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::IoError(error) => write!(formatter, "{}", error),
+            ReadError::ParseError(error) => write!(formatter, "{}", error),
+            ReadError::DuplicateTarget {
+                target,
+                first,
+                second,
+            } => write!(
+                formatter,
+                "{}: `{}` is already described at {}; remove one of them or \
+                 declare `Merge = \"append\"` or `Merge = \"prepend\"` to combine them",
+                second, target, first
+            ),
+            ReadError::UnsupportedPath(path) => write!(
+                formatter,
+                "{} is neither a file nor a directory",
+                path.display()
+            ),
+        }
+    }
 }
 
 impl From<io::Error> for ReadError {
@@ -76,37 +308,31 @@ impl From<ParseError> for ReadError {
 
 fn read_item_descriptions_from_path(
     path: &Path,
-    output: &mut HashMap<String, ItemDescription>,
+    output: &mut Vec<ItemDescription>,
 ) -> Result<(), ReadError> {
     let metadata = fs::metadata(path)?;
 
     if metadata.is_file() {
         // Only parse .md files.
-        if let Some(extension) = path.extension() {
-            if extension
-                .to_str()
-                .expect("File extension was not valid UTF-8.")
-                == "md"
-            {
-                let contents = fs::read_to_string(path)?;
-                parse_item_descriptions(&contents, output)?;
-            }
+        if path.extension() == Some(OsStr::new("md")) {
+            let contents = fs::read_to_string(path)?;
+            output.extend(parse_item_descriptions(&contents, Some(path))?);
         }
 
         Ok(())
     } else if metadata.is_dir() {
-        for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let entry_path = entry.path();
+        // Read entries in a stable order so that merged prose and duplicate
+        // reports don't depend on the file system.
+        let mut entry_paths = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        entry_paths.sort();
 
+        for entry_path in entry_paths {
             // Skip dot directories like ".git"
-            if let Some(entry_name) = path.file_name() {
-                if entry_name
-                    .to_str()
-                    .expect("Directory name was not valid UTF-8.")
-                    .starts_with('.')
-                {
-                    return Ok(());
+            if let Some(entry_name) = entry_path.file_name() {
+                if entry_name.to_string_lossy().starts_with('.') {
+                    continue;
                 }
             }
 
@@ -115,47 +341,516 @@ fn read_item_descriptions_from_path(
 
         Ok(())
     } else {
-        unimplemented!();
+        Err(ReadError::UnsupportedPath(path.to_path_buf()))
     }
 }
 
+/// Groups items by target, combining items that declare `Merge` with the main
+/// description of their target.
+///
+/// Each target can have at most one item without `Merge`. If every item for a
+/// target declares `Merge`, the first one is used as the main description.
+fn combine_item_descriptions(
+    items: Vec<ItemDescription>,
+) -> Result<HashMap<String, ItemDescription>, ReadError> {
+    let mut groups: Vec<Vec<ItemDescription>> = Vec::new();
+    let mut group_indices = HashMap::new();
+
+    for item in items {
+        let index = *group_indices
+            .entry(item.metadata.target.clone())
+            .or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+
+        groups[index].push(item);
+    }
+
+    let mut output = HashMap::new();
+
+    for group in groups {
+        let (mut main, additions): (Vec<_>, Vec<_>) = group
+            .into_iter()
+            .partition(|item| item.metadata.merge.is_none());
+
+        let mut additions = additions.into_iter();
+
+        if main.len() > 1 {
+            let second = main.swap_remove(1);
+            let first = main.swap_remove(0);
+
+            return Err(ReadError::DuplicateTarget {
+                target: first.metadata.target,
+                first: first.location,
+                second: second.location,
+            });
+        }
+
+        let mut combined = main
+            .pop()
+            .or_else(|| additions.next())
+            .expect("Groups always contain at least one item");
+
+        let mut prepended = Vec::new();
+        let mut appended = Vec::new();
+
+        for addition in additions {
+            match addition.metadata.merge {
+                Some(MergeMode::Prepend) => prepended.push(addition.prose),
+                _ => appended.push(addition.prose),
+            }
+
+            // Metadata from additions only fills in what the main description
+            // leaves unset.
+            if combined.metadata.return_types.is_none() {
+                combined.metadata.return_types = addition.metadata.return_types;
+            }
+
+            for (name, parameter) in addition.metadata.parameters {
+                combined
+                    .metadata
+                    .parameters
+                    .entry(name)
+                    .or_insert(parameter);
+            }
+        }
+
+        combined.prose = prepended
+            .into_iter()
+            .chain(Some(combined.prose))
+            .chain(appended)
+            .filter(|prose| !prose.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        output.insert(combined.metadata.target.clone(), combined);
+    }
+
+    Ok(output)
+}
+
+/// An error in a content file, positioned so that contributors can find it.
 #[derive(Debug)]
-pub enum ParseError {
+pub struct ParseError {
+    /// The file the error occurred in, if the content was read from disk.
+    pub path: Option<PathBuf>,
+
+    /// The 1-based line the error occurred on.
+    pub line: usize,
+
+    /// The 1-based column the error occurred on.
+    pub column: usize,
+
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    fn new(path: Option<&Path>, line: usize, column: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            path: path.map(Path::to_path_buf),
+            line,
+            column,
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(formatter, "{}:", path.display())?,
+            None => write!(formatter, "<input>:")?,
+        }
+
+        write!(formatter, "{}:{}: ", self.line, self.column)?;
+
+        match &self.kind {
+            ParseErrorKind::UnclosedMetadataBlock => {
+                write!(formatter, "metadata block is never closed")
+            }
+            ParseErrorKind::InvalidToml(error) => {
+                write!(formatter, "invalid metadata: {}", error)
+            }
+            ParseErrorKind::UnclosedCodeBlock => {
+                write!(formatter, "code block is never closed")
+            }
+            ParseErrorKind::ModeWithMerge => write!(
+                formatter,
+                "`Mode` and `Merge` can't both be declared; put `Mode` on the main \
+                 description of this target instead"
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseErrorKind {
     UnclosedMetadataBlock,
     InvalidToml(toml::de::Error),
+
+    /// A code block is still open at the end of the file, which would turn
+    /// every metadata block after it into prose.
+    UnclosedCodeBlock,
+
+    /// An item declares both `Mode` and `Merge`.
+    ModeWithMerge,
 }
 
-impl From<toml::de::Error> for ParseError {
-    fn from(error: toml::de::Error) -> ParseError {
-        ParseError::InvalidToml(error)
-    }
+/// Returns whether the given line is a metadata fence, which must be on a
+/// line of its own.
+fn is_metadata_fence(line: &str) -> bool {
+    line.trim() == METADATA_FENCE
+}
+
+/// Returns the marker that opens a Markdown code fence on this line, if any.
+fn code_fence_marker(line: &str) -> Option<&'static str> {
+    let line = line.trim_start();
+
+    ["```", "~~~"]
+        .iter()
+        .find(|marker| line.starts_with(*marker))
+        .cloned()
 }
 
 fn parse_item_descriptions(
     source: &str,
-    output: &mut HashMap<String, ItemDescription>,
-) -> Result<(), ParseError> {
-    let mut fence_locations = source.match_indices(METADATA_FENCE).peekable();
+    path: Option<&Path>,
+) -> Result<Vec<ItemDescription>, ParseError> {
+    let mut output = Vec::new();
+    let mut lines = source.lines().enumerate();
 
-    while let Some((start_index, fence)) = fence_locations.next() {
-        let (end_index, _) = fence_locations
-            .next()
-            .ok_or(ParseError::UnclosedMetadataBlock)?;
+    // The item whose prose we're currently collecting. Anything before the
+    // first metadata block isn't attached to an item and is ignored.
+    let mut current: Option<PendingItem> = None;
 
-        let metadata_source = &source[(start_index + fence.len())..end_index].trim();
-        let metadata: Metadata = toml::from_str(metadata_source)?;
+    // The marker of the code fence we're inside of and where it was opened,
+    // if any. Metadata fences inside code blocks are treated as prose.
+    let mut code_fence: Option<(&str, usize, usize)> = None;
 
-        let prose_after_end_index = match fence_locations.peek() {
-            Some((index, _)) => *index,
-            None => source.len(),
-        };
+    while let Some((index, line)) = lines.next() {
+        if code_fence.is_none() && is_metadata_fence(line) {
+            if let Some(item) = current.take() {
+                output.push(item.finish(path));
+            }
+
+            // Pad the block with blank lines so that positions in TOML errors
+            // line up with positions in the file.
+            let mut metadata_source = "\n".repeat(index + 1);
+
+            let metadata_end_index = loop {
+                match lines.next() {
+                    Some((end_index, line)) if is_metadata_fence(line) => break end_index,
+                    Some((_, line)) => {
+                        metadata_source.push_str(line);
+                        metadata_source.push('\n');
+                    }
+                    None => {
+                        return Err(ParseError::new(
+                            path,
+                            index + 1,
+                            1,
+                            ParseErrorKind::UnclosedMetadataBlock,
+                        ));
+                    }
+                }
+            };
 
-        let prose = source[(end_index + fence.len())..prose_after_end_index]
-            .trim()
-            .to_string();
+            let metadata: Metadata = toml::from_str(&metadata_source).map_err(|error| {
+                // Errors like missing fields don't have a position, so point
+                // at the start of the block instead.
+                let (line, column) = error
+                    .line_col()
+                    .map(|(line, column)| (line + 1, column + 1))
+                    .unwrap_or((index + 1, 1));
 
-        output.insert(metadata.target.clone(), ItemDescription { metadata, prose });
+                ParseError::new(path, line, column, ParseErrorKind::InvalidToml(error))
+            })?;
+
+            if metadata.mode.is_some() && metadata.merge.is_some() {
+                return Err(ParseError::new(
+                    path,
+                    index + 1,
+                    1,
+                    ParseErrorKind::ModeWithMerge,
+                ));
+            }
+
+            current = Some(PendingItem {
+                metadata,
+                line: index + 1,
+                metadata_end_line: metadata_end_index + 1,
+                prose: Vec::new(),
+            });
+            continue;
+        }
+
+        match code_fence {
+            Some((marker, _, _)) => {
+                if line.trim_start().starts_with(marker) {
+                    code_fence = None;
+                }
+            }
+            None => {
+                code_fence = code_fence_marker(line).map(|marker| {
+                    let column = line.len() - line.trim_start().len() + 1;
+                    (marker, index + 1, column)
+                });
+            }
+        }
+
+        if let Some(item) = &mut current {
+            item.prose.push(line);
+        }
+    }
+
+    if let Some((_, line, column)) = code_fence {
+        return Err(ParseError::new(
+            path,
+            line,
+            column,
+            ParseErrorKind::UnclosedCodeBlock,
+        ));
+    }
+
+    if let Some(item) = current {
+        output.push(item.finish(path));
+    }
+
+    Ok(output)
+}
+
+/// An item whose metadata has been parsed and whose prose is still being
+/// collected.
+struct PendingItem<'a> {
+    metadata: Metadata,
+    line: usize,
+    metadata_end_line: usize,
+    prose: Vec<&'a str>,
+}
+
+impl PendingItem<'_> {
+    fn finish(self, path: Option<&Path>) -> ItemDescription {
+        let prose_lines = self
+            .prose
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        ItemDescription {
+            metadata: self.metadata,
+            prose: self.prose.join("\n").trim().to_string(),
+            location: SourceLocation {
+                path: path.map(Path::to_path_buf),
+                line: self.line,
+                end_line: self.metadata_end_line + prose_lines,
+            },
+        }
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(source: &str) -> Result<HashMap<String, ItemDescription>, ReadError> {
+        combine_item_descriptions(parse_item_descriptions(source, None)?)
+    }
+
+    fn parse_error(source: &str) -> ParseError {
+        match parse(source) {
+            Err(ReadError::ParseError(error)) => error,
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn fences_must_be_on_their_own_line() {
+        let output = parse(
+            "+++\nTarget = \"Instance\"\n+++\n\nUse `a +++ b` to concatenate.\n\n\
+             +++\nTarget = \"Instance.Name\"\n+++\nA name.\n",
+        )
+        .unwrap();
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(output["Instance"].prose, "Use `a +++ b` to concatenate.");
+        assert_eq!(output["Instance.Name"].prose, "A name.");
+
+        let location = &output["Instance"].location;
+        assert_eq!((location.line, location.end_line), (1, 5));
+    }
+
+    #[test]
+    fn fences_inside_code_blocks_are_prose() {
+        let source = "+++\nTarget = \"Instance\"\n+++\n\
+                      ```md\n+++\nTarget = \"Example\"\n+++\n```\n";
+        let output = parse(source).unwrap();
+
+        assert_eq!(output.len(), 1);
+        assert!(output["Instance"].prose.contains("Target = \"Example\""));
+    }
+
+    #[test]
+    fn error_positions() {
+        let error = parse_error("# Heading\n\n+++\nTarget = \"Instance\"\nReturnTypes = [\n");
+        match error.kind {
+            ParseErrorKind::UnclosedMetadataBlock => {}
+            _ => panic!("expected an unclosed metadata block, got {:?}", error),
+        }
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let error = parse_error("+++\nTarget = \"Instance\"\nReturnTypes = ?\n+++\n");
+        match error.kind {
+            ParseErrorKind::InvalidToml(_) => {}
+            _ => panic!("expected invalid TOML, got {:?}", error),
+        }
+        assert_eq!((error.line, error.column), (3, 15));
+
+        let error = parse_error(
+            "+++\nTarget = \"Instance\"\n+++\n  ```lua\nprint()\n\n\
+             +++\nTarget = \"Instance.Name\"\n+++\nA name.\n",
+        );
+        match error.kind {
+            ParseErrorKind::UnclosedCodeBlock => {}
+            _ => panic!("expected an unclosed code block, got {:?}", error),
+        }
+        assert_eq!((error.line, error.column), (4, 3));
+    }
+
+    #[test]
+    fn duplicate_targets() {
+        let error =
+            parse("+++\nTarget = \"Part\"\n+++\nOne.\n\n+++\nTarget = \"Part\"\n+++\nTwo.\n")
+                .unwrap_err();
+
+        match error {
+            ReadError::DuplicateTarget {
+                target,
+                first,
+                second,
+            } => {
+                assert_eq!(target, "Part");
+                assert_eq!(first.line, 1);
+                assert_eq!(second.line, 6);
+            }
+            other => panic!("expected a duplicate target, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn merged_targets() {
+        let output = parse(
+            "+++\nTarget = \"Part\"\nMerge = \"append\"\n+++\nAppended.\n\n\
+             +++\nTarget = \"Part\"\nMerge = \"prepend\"\n+++\nPrepended.\n\n\
+             +++\nTarget = \"Part\"\nReturnTypes = [\"bool\"]\n+++\nOriginal.\n",
+        )
+        .unwrap();
+
+        let part = &output["Part"];
+        assert_eq!(part.prose, "Prepended.\n\nOriginal.\n\nAppended.");
+        assert_eq!(part.location.line, 13);
+        assert!(part.metadata.return_types.is_some());
+    }
+
+    #[test]
+    fn relative_origins() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::create_dir(directory.path().join("Instance")).unwrap();
+        fs::write(
+            directory.path().join("Instance").join("Name.md"),
+            "+++\nTarget = \"Instance.Name\"\n+++\n\nA name.\n\nMore about names.\n\n",
+        )
+        .unwrap();
+
+        let content = SupplementalData::read_from_path(directory.path()).unwrap();
+        let origin = content
+            .origin(&content.item_descriptions["Instance.Name"])
+            .unwrap();
+
+        assert_eq!(origin.path, "Instance/Name.md");
+        assert_eq!((origin.start_line, origin.end_line), (1, 7));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unusual_paths() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(
+            directory.path().join(OsStr::from_bytes(b"\xff.md")),
+            "+++\nTarget = \"Instance\"\n+++\nAn instance.\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.path().join(OsStr::from_bytes(b"notes.\xff")),
+            "Not content.",
+        )
+        .unwrap();
+
+        let content = SupplementalData::read_from_path(directory.path()).unwrap();
+        assert_eq!(content.item_descriptions.len(), 1);
+
+        match SupplementalData::read_from_path(Path::new("/dev/null")) {
+            Err(ReadError::UnsupportedPath(path)) => assert_eq!(path, Path::new("/dev/null")),
+            other => panic!("expected an unsupported path error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn description_modes() {
+        let output = parse(
+            "+++\nTarget = \"Part\"\nMode = \"append\"\n+++\nMore.\n\n\
+             +++\nTarget = \"Part.Size\"\n+++\nSize.\n",
+        )
+        .unwrap();
+
+        let part = output["Part"].metadata.mode.unwrap();
+        assert_eq!(part, DescriptionMode::Append);
+        assert_eq!(
+            part.combine(Some("Original."), "More."),
+            "Original.\n\nMore."
+        );
+        assert_eq!(part.combine(None, "More."), "More.");
+
+        let size = output["Part.Size"].metadata.mode.unwrap_or_default();
+        assert_eq!(size, DescriptionMode::Replace);
+        assert_eq!(size.combine(Some("Original."), "Size."), "Size.");
+
+        let error = parse_error(
+            "+++\nTarget = \"Part\"\n+++\nOne.\n\n\
+             +++\nTarget = \"Part\"\nMerge = \"append\"\nMode = \"prepend\"\n+++\nTwo.\n",
+        );
+        match error.kind {
+            ParseErrorKind::ModeWithMerge => {}
+            _ => panic!("expected Mode and Merge to conflict, got {:?}", error),
+        }
+        assert_eq!((error.line, error.column), (6, 1));
+    }
+
+    #[test]
+    fn parameters() {
+        let output = parse(
+            "+++\nTarget = \"Instance.FindFirstChild\"\n\n\
+             [Parameters.name]\nDescription = \"The name to look for.\"\nType = \"string\"\n\n\
+             [Parameters.recursive]\nOptional = true\nDefault = \"false\"\n+++\nFinds a child.\n",
+        )
+        .unwrap();
+
+        let parameters = &output["Instance.FindFirstChild"].metadata.parameters;
+        assert_eq!(parameters.len(), 2);
+
+        let name = &parameters["name"];
+        assert_eq!(name.description.as_deref(), Some("The name to look for."));
+        assert_eq!(name.kind.as_deref(), Some("string"));
+        assert_eq!(name.optional, None);
+        assert_eq!(name.default, None);
+
+        let recursive = &parameters["recursive"];
+        assert_eq!(recursive.description, None);
+        assert_eq!(recursive.kind, None);
+        assert_eq!(recursive.optional, Some(true));
+        assert_eq!(recursive.default.as_deref(), Some("false"));
+    }
 }